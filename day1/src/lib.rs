@@ -0,0 +1,2305 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+// ========== 회전 명령 파싱 ==========
+
+/// 회전 방향
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Direction::Left => write!(f, "L"),
+            Direction::Right => write!(f, "R"),
+        }
+    }
+}
+
+/// 다이얼 명령 한 줄
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    /// `L68`
+    Left(u64),
+    /// `R48`
+    Right(u64),
+    /// `R48x3`: 같은 회전을 times번 반복
+    Repeat {
+        direction: Direction,
+        amount: u64,
+        times: u64,
+    },
+    /// `=37`: 지정한 위치로 바로 이동 (다이얼 크기로 나눈 나머지)
+    Set(u64),
+    /// `RESET`: 시작 위치로 바로 이동
+    Reset,
+}
+
+impl fmt::Display for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rotation::Left(amount) => write!(f, "L{}", amount),
+            Rotation::Right(amount) => write!(f, "R{}", amount),
+            Rotation::Repeat {
+                direction,
+                amount,
+                times,
+            } => write!(f, "{}{}x{}", direction, amount, times),
+            Rotation::Set(position) => write!(f, "={}", position),
+            Rotation::Reset => write!(f, "RESET"),
+        }
+    }
+}
+
+impl Rotation {
+    /// 회전 명령이면 (방향, 회전량, 반복 횟수), 바로 이동하는 명령이면 None
+    fn as_turn(&self) -> Option<(Direction, u64, u64)> {
+        match *self {
+            Rotation::Left(amount) => Some((Direction::Left, amount, 1)),
+            Rotation::Right(amount) => Some((Direction::Right, amount, 1)),
+            Rotation::Repeat {
+                direction,
+                amount,
+                times,
+            } => Some((direction, amount, times)),
+            Rotation::Set(_) | Rotation::Reset => None,
+        }
+    }
+}
+
+/// 파싱 실패 이유
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// 빈 줄
+    Empty,
+    /// 명령이 대문자 R/L, = 또는 RESET으로 시작하지 않음
+    InvalidDirection(char),
+    /// 방향, `=` 또는 `x` 뒤에 숫자가 없음
+    MissingAmount,
+    /// 숫자 자리에 숫자가 아닌 문자가 있음
+    InvalidDigit(char),
+    /// 숫자가 u64 범위를 넘음
+    AmountTooLarge,
+    /// 다이얼 번호 뒤에 `:`가 없음
+    MissingColon,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::Empty => write!(f, "빈 줄"),
+            ParseErrorKind::InvalidDirection(c) => {
+                write!(f, "명령은 'L', 'R', '=' 또는 RESET이어야 함: {:?}", c)
+            }
+            ParseErrorKind::MissingAmount => write!(f, "숫자 없음"),
+            ParseErrorKind::InvalidDigit(c) => write!(f, "숫자가 아닌 문자: {:?}", c),
+            ParseErrorKind::AmountTooLarge => write!(f, "숫자가 너무 큼"),
+            ParseErrorKind::MissingColon => write!(f, "다이얼 번호 뒤에 ':' 없음"),
+        }
+    }
+}
+
+/// 회전 명령 파싱 에러 (줄 번호와 열 번호는 1부터, 열은 문자 단위)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRotationError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseRotationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl std::error::Error for ParseRotationError {}
+
+/// 문자를 열 번호와 함께 읽는 커서
+struct Cursor<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    /// 다음 문자의 열 번호
+    column: usize,
+}
+
+impl Cursor<'_> {
+    /// 10진수 하나를 읽음, 숫자가 아닌 문자 앞에서 멈춤
+    fn number(&mut self) -> Result<u64, (usize, ParseErrorKind)> {
+        let start = self.column;
+        let mut value: u64 = 0;
+        while let Some(digit) = self.chars.peek().and_then(|c| c.to_digit(10)) {
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(digit as u64))
+                .ok_or((start, ParseErrorKind::AmountTooLarge))?;
+            self.chars.next();
+            self.column += 1;
+        }
+        if self.column == start {
+            return Err(match self.chars.peek() {
+                Some(&c) => (start, ParseErrorKind::InvalidDigit(c)),
+                None => (start, ParseErrorKind::MissingAmount),
+            });
+        }
+        Ok(value)
+    }
+
+    /// 줄 끝이어야 함
+    fn end(&mut self) -> Result<(), (usize, ParseErrorKind)> {
+        match self.chars.peek() {
+            Some(&c) => Err((self.column, ParseErrorKind::InvalidDigit(c))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// 한 줄을 파싱, 실패하면 (열 번호, 이유) 반환
+///
+/// 문법: `L<n>`, `R<n>`, `L<n>x<k>`, `R<n>x<k>`, `=<n>`, `RESET`
+fn parse_line(line: &str) -> Result<Rotation, (usize, ParseErrorKind)> {
+    if line == "RESET" {
+        return Ok(Rotation::Reset);
+    }
+
+    let mut cursor = Cursor {
+        chars: line.chars().peekable(),
+        column: 2,
+    };
+    let first = cursor.chars.next().ok_or((1, ParseErrorKind::Empty))?;
+    let direction = match first {
+        'L' => Direction::Left,
+        'R' => Direction::Right,
+        '=' => {
+            let position = cursor.number()?;
+            cursor.end()?;
+            return Ok(Rotation::Set(position));
+        }
+        c => return Err((1, ParseErrorKind::InvalidDirection(c))),
+    };
+
+    let amount = cursor.number()?;
+    if cursor.chars.next_if_eq(&'x').is_none() {
+        cursor.end()?;
+        return Ok(match direction {
+            Direction::Left => Rotation::Left(amount),
+            Direction::Right => Rotation::Right(amount),
+        });
+    }
+    cursor.column += 1;
+    let times = cursor.number()?;
+    cursor.end()?;
+
+    Ok(Rotation::Repeat {
+        direction,
+        amount,
+        times,
+    })
+}
+
+/// 한 줄짜리 입력으로 파싱 (에러의 줄 번호는 1)
+impl FromStr for Rotation {
+    type Err = ParseRotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_line(s).map_err(|(column, kind)| ParseRotationError {
+            line: 1,
+            column,
+            kind,
+        })
+    }
+}
+
+/// 줄마다 명령을 파싱하는 이터레이터 (줄 번호는 1부터)
+///
+/// `T`는 한 줄짜리 FromStr 파서를 가진 명령 타입 (Rotation, LockCommand)
+pub struct Rotations<I, T = Rotation> {
+    lines: I,
+    line: usize,
+    command: PhantomData<T>,
+}
+
+impl<I, T> Iterator for Rotations<I, T>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+    T: FromStr<Err = ParseRotationError>,
+{
+    type Item = Result<T, ParseRotationError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let text = self.lines.next()?;
+        self.line += 1;
+        let line = self.line;
+        Some(
+            text.as_ref()
+                .parse()
+                .map_err(|error| ParseRotationError { line, ..error }),
+        )
+    }
+}
+
+/// 줄 이터레이터를 필요할 때마다 파싱하는 이터레이터로 변환
+pub fn rotations_from_lines<I>(lines: I) -> Rotations<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    Rotations {
+        lines: lines.into_iter(),
+        line: 0,
+        command: PhantomData,
+    }
+}
+
+/// 다이얼 번호가 붙은 명령을 줄마다 파싱
+pub fn lock_commands_from_lines<I>(lines: I) -> Rotations<I::IntoIter, LockCommand>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    Rotations {
+        lines: lines.into_iter(),
+        line: 0,
+        command: PhantomData,
+    }
+}
+
+/// 여러 줄 입력을 파싱
+/// 하나라도 실패하면 잘못된 줄 전부의 에러를 반환
+pub fn parse_rotations(input: &str) -> Result<Vec<Rotation>, Vec<ParseRotationError>> {
+    collect_commands(rotations_from_lines(input.lines()))
+}
+
+/// 다이얼 번호가 붙은 여러 줄 입력을 파싱
+/// 하나라도 실패하면 잘못된 줄 전부의 에러를 반환
+pub fn parse_lock_commands(input: &str) -> Result<Vec<LockCommand>, Vec<ParseRotationError>> {
+    collect_commands(lock_commands_from_lines(input.lines()))
+}
+
+fn collect_commands<T>(
+    results: impl Iterator<Item = Result<T, ParseRotationError>>,
+) -> Result<Vec<T>, Vec<ParseRotationError>> {
+    let mut commands = Vec::new();
+    let mut errors = Vec::new();
+
+    for result in results {
+        match result {
+            Ok(command) => commands.push(command),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(commands)
+    } else {
+        Err(errors)
+    }
+}
+
+/// 횟수가 u64 범위를 넘음
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowError;
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "횟수가 u64 범위를 넘음")
+    }
+}
+
+impl std::error::Error for OverflowError {}
+
+/// 입력을 읽어 실행할 때의 에러: 읽기 실패, 파싱 실패, 오버플로 또는 없는 다이얼 번호
+#[derive(Debug)]
+pub enum DialError {
+    Io(io::Error),
+    Parse(ParseRotationError),
+    Overflow(OverflowError),
+    NoSuchDial { index: usize, dials: usize },
+}
+
+impl fmt::Display for DialError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DialError::Io(error) => write!(f, "읽기 실패: {}", error),
+            DialError::Parse(error) => write!(f, "{}", error),
+            DialError::Overflow(error) => write!(f, "{}", error),
+            DialError::NoSuchDial { index, dials } => {
+                write!(f, "{}번 다이얼 없음 (다이얼 {}개)", index, dials)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DialError {}
+
+impl From<io::Error> for DialError {
+    fn from(error: io::Error) -> Self {
+        DialError::Io(error)
+    }
+}
+
+impl From<ParseRotationError> for DialError {
+    fn from(error: ParseRotationError) -> Self {
+        DialError::Parse(error)
+    }
+}
+
+impl From<OverflowError> for DialError {
+    fn from(error: OverflowError) -> Self {
+        DialError::Overflow(error)
+    }
+}
+
+/// 오버플로를 에러로 바꾼 덧셈
+fn add(a: u64, b: u64) -> Result<u64, OverflowError> {
+    a.checked_add(b).ok_or(OverflowError)
+}
+
+/// BufRead에서 한 줄씩 읽어 파싱하는 이터레이터
+/// 줄 버퍼 하나를 재사용하므로 입력 크기와 관계없이 메모리 일정
+pub struct ReadRotations<R, T = Rotation> {
+    reader: R,
+    buffer: String,
+    line: usize,
+    command: PhantomData<T>,
+}
+
+impl<R, T> Iterator for ReadRotations<R, T>
+where
+    R: BufRead,
+    T: FromStr<Err = ParseRotationError>,
+{
+    type Item = Result<T, DialError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buffer.clear();
+        match self.reader.read_line(&mut self.buffer) {
+            Ok(0) => return None,
+            Ok(_) => {}
+            Err(error) => return Some(Err(error.into())),
+        }
+        self.line += 1;
+
+        // str::lines와 같이 줄 끝의 \n, \r\n 제거
+        let text = self.buffer.strip_suffix('\n').unwrap_or(&self.buffer);
+        let text = text.strip_suffix('\r').unwrap_or(text);
+        let line = self.line;
+        Some(
+            text.parse()
+                .map_err(|error| ParseRotationError { line, ..error }.into()),
+        )
+    }
+}
+
+/// reader를 한 줄씩 읽어 Rotation으로 파싱
+pub fn read_rotations<R: BufRead>(reader: R) -> ReadRotations<R> {
+    ReadRotations {
+        reader,
+        buffer: String::new(),
+        line: 0,
+        command: PhantomData,
+    }
+}
+
+/// reader를 한 줄씩 읽어 다이얼 번호가 붙은 명령으로 파싱
+pub fn read_lock_commands<R: BufRead>(reader: R) -> ReadRotations<R, LockCommand> {
+    ReadRotations {
+        reader,
+        buffer: String::new(),
+        line: 0,
+        command: PhantomData,
+    }
+}
+
+// ========== 다이얼 ==========
+
+/// 다이얼: 0..size 눈금을 가진 원형 자물쇠
+/// 기본값은 100칸, 시작 위치 50
+///
+/// 위치와 회전량은 u64, 내부 계산은 u128이라 중간값은 넘치지 않음.
+/// 결과 횟수가 u64를 넘으면 OverflowError.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dial {
+    size: u64,
+    start: u64,
+    position: u64,
+}
+
+impl Dial {
+    /// size칸 다이얼을 start 위치에서 시작
+    pub fn new(size: u64, start: u64) -> Self {
+        assert!(size > 0, "다이얼 크기는 1 이상이어야 함: {}", size);
+        assert!(
+            start < size,
+            "시작 위치가 범위를 벗어남: {} (크기 {})",
+            start,
+            size
+        );
+        Dial {
+            size,
+            start,
+            position: start,
+        }
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// RESET 명령이 돌아가는 위치
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// 오른쪽으로 amount칸 회전, 0을 거친 횟수 반환 (0에 도착한 경우 포함)
+    pub fn rotate_right(&mut self, amount: u64) -> u64 {
+        // 한 번 회전은 (position + amount) / size <= u64::MAX 이므로 넘치지 않음
+        self.turn(Direction::Right, amount, 1).unwrap().1
+    }
+
+    /// 왼쪽으로 amount칸 회전, 0을 거친 횟수 반환 (0에 도착한 경우 포함)
+    pub fn rotate_left(&mut self, amount: u64) -> u64 {
+        self.turn(Direction::Left, amount, 1).unwrap().1
+    }
+
+    /// 같은 회전을 times번 반복, (0에 멈춘 횟수, 0을 거친 횟수) 반환
+    ///
+    /// 반복 횟수와 관계없이 O(log size):
+    /// - 0을 거친 횟수는 amount * times칸을 한 번에 돈 것과 같음
+    /// - 0에 멈춘 횟수는 선형 합동식 j * step ≡ -position (mod size)의 해 개수
+    fn turn(
+        &mut self,
+        direction: Direction,
+        amount: u64,
+        times: u64,
+    ) -> Result<(u64, u64), OverflowError> {
+        let size = self.size as u128;
+        let position = self.position as u128;
+        // (2^64 - 1)^2 + 2^64 < 2^128
+        let distance = amount as u128 * times as u128;
+
+        // 왼쪽 회전은 거울상 위치 (size - position) % size에서 오른쪽 회전한 것과 같음
+        // 0에서 출발하면 거울상도 0이므로 출발 지점은 세지 않음
+        let (passes, new_position, step) = match direction {
+            Direction::Right => (
+                (position + distance) / size,
+                (position + distance) % size,
+                amount as u128 % size,
+            ),
+            Direction::Left => (
+                ((size - position) % size + distance) / size,
+                (position + size - distance % size) % size,
+                (size - amount as u128 % size) % size,
+            ),
+        };
+        let passes = u64::try_from(passes).map_err(|_| OverflowError)?;
+        let landings = count_landings(position, step, size, times as u128) as u64;
+
+        self.position = new_position as u64;
+        Ok((landings, passes))
+    }
+
+    /// 회전 없이 target으로 바로 이동, 0으로 이동하면 멈춘 것이자 거친 것으로 셈
+    fn jump(&mut self, target: u64) -> (u64, u64) {
+        self.position = target % self.size;
+        let hit = (self.position == 0) as u64;
+        (hit, hit)
+    }
+
+    /// 명령 하나를 실행하고 그 기록을 반환
+    /// 0을 거친 횟수가 u64를 넘으면 다이얼은 바뀌지 않음
+    pub fn apply(&mut self, rotation: Rotation) -> Result<DialEvent, OverflowError> {
+        let from = self.position;
+        let (landings, zero_hits) = match rotation {
+            Rotation::Set(target) => self.jump(target),
+            Rotation::Reset => self.jump(self.start),
+            _ => {
+                let (direction, amount, times) = rotation.as_turn().unwrap();
+                self.turn(direction, amount, times)?
+            }
+        };
+
+        Ok(DialEvent {
+            rotation,
+            from,
+            to: self.position,
+            landings,
+            zero_hits,
+        })
+    }
+}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Dial::new(100, 50)
+    }
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// x * inverse ≡ 1 (mod m), gcd(x, m) = 1 가정 (확장 유클리드)
+fn mod_inverse(x: u128, m: u128) -> u128 {
+    // m < 2^64이므로 계수는 i128에 들어감
+    let (mut old_r, mut r) = (x as i128, m as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+    }
+    old_s.rem_euclid(m as i128) as u128
+}
+
+/// j = 1..=times 중 (position + j * step) % size == 0인 j의 개수 (times 이하)
+fn count_landings(position: u128, step: u128, size: u128, times: u128) -> u128 {
+    let target = (size - position) % size;
+    let g = gcd(step, size);
+    if !target.is_multiple_of(g) {
+        return 0;
+    }
+
+    // 해는 period마다 반복됨, 가장 작은 양의 해 first를 구해 등차수열로 셈
+    let period = size / g;
+    let first = match (target / g) * mod_inverse(step / g, period) % period {
+        0 => period,
+        j => j,
+    };
+    if first > times {
+        0
+    } else {
+        (times - first) / period + 1
+    }
+}
+
+/// 명령 하나를 실행한 기록
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DialEvent {
+    /// 실행한 명령
+    pub rotation: Rotation,
+    /// 회전 전 위치
+    pub from: u64,
+    /// 회전 후 위치
+    pub to: u64,
+    /// 회전 후 0에 멈춘 횟수 (반복 명령은 매 회전마다 셈)
+    pub landings: u64,
+    /// 회전 중 0에 도착하거나 0을 지나간 횟수
+    pub zero_hits: u64,
+}
+
+/// 명령마다 DialEvent를 하나씩 내놓는 이터레이터
+pub struct Trace<I> {
+    dial: Dial,
+    rotations: I,
+}
+
+impl<I> Trace<I> {
+    /// 지금까지의 명령을 실행한 다이얼 상태
+    pub fn dial(&self) -> &Dial {
+        &self.dial
+    }
+}
+
+impl<I: Iterator<Item = Rotation>> Iterator for Trace<I> {
+    type Item = Result<DialEvent, OverflowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rotation = self.rotations.next()?;
+        Some(self.dial.apply(rotation))
+    }
+}
+
+/// dial에서 시작해 rotations를 순서대로 실행하는 이벤트 이터레이터
+pub fn trace<I: IntoIterator<Item = Rotation>>(dial: Dial, rotations: I) -> Trace<I::IntoIter> {
+    Trace {
+        dial,
+        rotations: rotations.into_iter(),
+    }
+}
+
+/// Part 1: 기본 다이얼(100칸, 시작 50)에서 회전 후 0에 멈춘 횟수
+/// 줄을 하나씩 파싱하며 실행, 첫 에러에서 중단
+pub fn password_part1<I>(lines: I) -> Result<(u64, u64), DialError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    count_events(Dial::default(), rotations_from_lines(lines), |e| e.landings)
+}
+
+/// Part 2: 기본 다이얼(100칸, 시작 50)에서 0을 거친 횟수
+/// 줄을 하나씩 파싱하며 실행, 첫 에러에서 중단
+pub fn password_part2<I>(lines: I) -> Result<(u64, u64), DialError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    count_events(Dial::default(), rotations_from_lines(lines), |e| {
+        e.zero_hits
+    })
+}
+
+/// Part 1 (BufRead): 한 줄씩 읽으며 실행, 메모리 일정
+pub fn password_part1_reader<R: BufRead>(reader: R) -> Result<(u64, u64), DialError> {
+    count_events(Dial::default(), read_rotations(reader), |e| e.landings)
+}
+
+/// Part 2 (BufRead): 한 줄씩 읽으며 실행, 메모리 일정
+pub fn password_part2_reader<R: BufRead>(reader: R) -> Result<(u64, u64), DialError> {
+    count_events(Dial::default(), read_rotations(reader), |e| e.zero_hits)
+}
+
+/// Part 1 (임의 다이얼): 회전 후 0에 멈춘 횟수
+pub fn password_part1_with<I: IntoIterator<Item = Rotation>>(
+    dial: Dial,
+    rotations: I,
+) -> Result<(u64, u64), OverflowError> {
+    count_events(
+        dial,
+        rotations.into_iter().map(Ok::<_, OverflowError>),
+        |e| e.landings,
+    )
+}
+
+/// Part 2 (임의 다이얼): 회전 중 0을 거친 횟수
+pub fn password_part2_with<I: IntoIterator<Item = Rotation>>(
+    dial: Dial,
+    rotations: I,
+) -> Result<(u64, u64), OverflowError> {
+    count_events(
+        dial,
+        rotations.into_iter().map(Ok::<_, OverflowError>),
+        |e| e.zero_hits,
+    )
+}
+
+/// 파싱 결과를 trace로 실행하며 이벤트마다 count를 더함, 첫 에러에서 중단
+/// (--trace 출력과 같은 이벤트에서 합계를 구함)
+fn count_events<I, T, E>(
+    dial: Dial,
+    results: I,
+    count: fn(&DialEvent) -> u64,
+) -> Result<(u64, u64), E>
+where
+    I: IntoIterator<Item = Result<Rotation, T>>,
+    E: From<T> + From<OverflowError>,
+{
+    // 파싱 에러가 나면 거기서 명령을 끊고 에러를 보관
+    let mut parse_error = None;
+    let rotations = results
+        .into_iter()
+        .map_while(|result| result.map_err(|error| parse_error = Some(error)).ok());
+
+    let mut events = trace(dial, rotations);
+    let mut total = 0;
+    for event in events.by_ref() {
+        total = add(total, count(&event?))?;
+    }
+    let position = events.dial().position();
+    drop(events);
+
+    match parse_error {
+        Some(error) => Err(error.into()),
+        None => Ok((position, total)),
+    }
+}
+
+// ========== 브루트포스 버전 ==========
+
+/// 기준 구현: 한 칸씩 돌리며 0을 직접 셈
+/// (최종 위치, 0에 멈춘 횟수, 0을 거친 횟수), O(전체 회전량)
+///
+/// 닫힌 공식(Dial::apply)과 비교하는 테스트용, 큰 회전량에는 쓰지 말 것
+pub fn simulate_clicks<I: IntoIterator<Item = Rotation>>(
+    dial: Dial,
+    rotations: I,
+) -> (u64, u64, u64) {
+    let size = dial.size();
+    let mut position = dial.position();
+    let (mut landings, mut passes) = (0, 0);
+
+    for rotation in rotations {
+        let Some((direction, amount, times)) = rotation.as_turn() else {
+            position = match rotation {
+                Rotation::Set(target) => target % size,
+                _ => dial.start(),
+            };
+            if position == 0 {
+                landings += 1;
+                passes += 1;
+            }
+            continue;
+        };
+
+        for _ in 0..times {
+            for _ in 0..amount {
+                position = match direction {
+                    Direction::Right => (position + 1) % size,
+                    Direction::Left => (position + size - 1) % size,
+                };
+                if position == 0 {
+                    passes += 1;
+                }
+            }
+            if position == 0 {
+                landings += 1;
+            }
+        }
+    }
+
+    (position, landings, passes)
+}
+
+// ========== 위치별 히스토그램 ==========
+
+/// 위치별 통계: index가 다이얼 위치
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    /// 명령 후 그 위치에 멈춘 횟수 (landings[0]은 Part 1 답)
+    pub landings: Vec<u64>,
+    /// 회전 중 그 위치에 도착하거나 지나간 횟수 (passes[0]은 Part 2 답)
+    pub passes: Vec<u64>,
+}
+
+/// 모든 위치에 대해 멈춘 횟수와 지나간 횟수를 한 번에 계산
+///
+/// 한 칸씩 움직이지 않고 명령마다 차분 배열(difference array)에 기록:
+/// - 회전량 d = q * size + r이면 모든 위치 +q, 출발 다음 칸부터 r칸 구간 +1
+/// - 한 번 회전하는 명령은 도착 위치에 바로 +1
+/// - 2번 이상 반복하는 명령의 멈춘 위치는 step의 궤도 위에서 연속 구간이므로
+///   step별 궤도 차분 배열에 O(1)로 기록 (`Orbits`, step마다 크기 size인 표 하나)
+///
+/// O(명령 수 + size × 반복 명령의 서로 다른 step 수),
+/// 어느 위치든 횟수가 u64를 넘으면 OverflowError
+pub fn histogram<I: IntoIterator<Item = Rotation>>(
+    dial: Dial,
+    rotations: I,
+) -> Result<Histogram, OverflowError> {
+    Ok(histogram_with_dial(dial, rotations)?.0)
+}
+
+/// histogram과 같지만 명령을 모두 실행한 다이얼도 함께 반환
+fn histogram_with_dial<I: IntoIterator<Item = Rotation>>(
+    dial: Dial,
+    rotations: I,
+) -> Result<(Histogram, Dial), OverflowError> {
+    let size = dial.size() as usize;
+    let mut landings = vec![0u64; size];
+    // passes의 차분 배열, diff[i] - diff[i-1]만큼 위치 i부터 더해짐
+    // 구간 하나가 +1씩이므로 명령 수를 넘지 않음
+    let mut diff = vec![0i64; size + 1];
+    // 모든 위치에 공통으로 더해지는 바퀴 수
+    let mut laps = 0u64;
+
+    // step -> 그 step으로 2번 이상 반복한 명령들의 멈춘 위치
+    let mut orbits: HashMap<usize, Orbits> = HashMap::new();
+
+    let mut events = trace(dial, rotations);
+    for event in events.by_ref() {
+        let event = event?;
+        let from = event.from as usize;
+        let to = event.to as usize;
+
+        let Some((direction, amount, times)) = event.rotation.as_turn() else {
+            // 바로 이동: 도착 위치에 멈추고 지나간 것으로 셈
+            landings[to] = add(landings[to], 1)?;
+            add_arc(&mut diff, to, 1);
+            continue;
+        };
+
+        let distance = amount as u128 * times as u128;
+        let lap = u64::try_from(distance / size as u128).map_err(|_| OverflowError)?;
+        laps = add(laps, lap)?;
+        let rest = (distance % size as u128) as usize;
+        match direction {
+            // from+1 ..= from+rest
+            Direction::Right => add_arc(&mut diff, (from + 1) % size, rest),
+            // from-rest ..= from-1
+            Direction::Left => add_arc(&mut diff, (from + size - rest) % size, rest),
+        }
+
+        // 한 번 회전: 도착 위치에 멈춤
+        if times <= 1 {
+            landings[to] = add(landings[to], times)?;
+            continue;
+        }
+
+        // 멈춘 위치: from + j * step (j = 1..=times)
+        let step = (match direction {
+            Direction::Right => amount % size as u64,
+            Direction::Left => (size as u64 - amount % size as u64) % size as u64,
+        }) as usize;
+        orbits
+            .entry(step)
+            .or_insert_with(|| Orbits::new(step, size))
+            .record(from, times)?;
+    }
+
+    for orbit in orbits.values() {
+        orbit.spread(&mut landings)?;
+    }
+
+    let mut passes = Vec::with_capacity(size);
+    let mut running = laps as i128;
+    for d in &diff[..size] {
+        running += *d as i128;
+        passes.push(u64::try_from(running).map_err(|_| OverflowError)?);
+    }
+
+    Ok((Histogram { landings, passes }, *events.dial()))
+}
+
+/// 원형 차분 배열 diff (길이 = 원 크기 + 1)의 구간 [from, from + len)에 +1
+fn add_arc(diff: &mut [i64], from: usize, len: usize) {
+    let size = diff.len() - 1;
+    if len == 0 {
+        return;
+    }
+    let end = from + len;
+    diff[from] += 1;
+    if end <= size {
+        diff[end] -= 1;
+    } else {
+        diff[size] -= 1;
+        diff[0] += 1;
+        diff[end - size] -= 1;
+    }
+}
+
+/// 같은 step으로 반복하는 명령들의 멈춘 위치 (histogram용)
+///
+/// g = gcd(step, size), period = size / g일 때 위치는 궤도 r (< g)의
+/// t번째 칸 r + t * step으로 쓸 수 있음. from에서 j = 1..=times번 멈춘 위치는
+/// from의 칸 t에서 시작하는 궤도 위 구간이므로:
+/// - times / period 바퀴는 궤도 전체에 더함
+/// - 나머지 times % period번은 칸 [t + 1, t + 나머지]에 차분 배열로 +1
+struct Orbits {
+    step: usize,
+    size: usize,
+    g: usize,
+    period: usize,
+    /// (step / g)의 mod period 역원: 위치에서 칸 번호를 구할 때 사용
+    inverse: u128,
+    /// 궤도마다 전체에 더할 바퀴 수
+    laps: Vec<u64>,
+    /// 궤도 r의 차분 배열은 diff[r * (period + 1)..(r + 1) * (period + 1)]
+    diff: Vec<i64>,
+}
+
+impl Orbits {
+    fn new(step: usize, size: usize) -> Self {
+        let g = gcd(step as u128, size as u128) as usize;
+        let period = size / g;
+        Orbits {
+            step,
+            size,
+            g,
+            period,
+            inverse: mod_inverse((step / g) as u128, period as u128),
+            laps: vec![0; g],
+            diff: vec![0; g * (period + 1)],
+        }
+    }
+
+    /// from에서 step만큼 times번 이동하며 멈춤
+    fn record(&mut self, from: usize, times: u64) -> Result<(), OverflowError> {
+        let orbit = from % self.g;
+        let cell = ((from / self.g) as u128 * self.inverse % self.period as u128) as usize;
+        let period = self.period as u64;
+        self.laps[orbit] = add(self.laps[orbit], times / period)?;
+        let width = self.period + 1;
+        add_arc(
+            &mut self.diff[orbit * width..(orbit + 1) * width],
+            (cell + 1) % self.period,
+            (times % period) as usize,
+        );
+        Ok(())
+    }
+
+    /// 모은 횟수를 위치별 landings에 더함, O(size)
+    fn spread(&self, landings: &mut [u64]) -> Result<(), OverflowError> {
+        let width = self.period + 1;
+        for orbit in 0..self.g {
+            let mut running = 0i64;
+            let mut position = orbit;
+            for d in &self.diff[orbit * width..orbit * width + self.period] {
+                running += d;
+                let count = add(self.laps[orbit], running as u64)?;
+                landings[position] = add(landings[position], count)?;
+                position = (position + self.step) % self.size;
+            }
+        }
+        Ok(())
+    }
+}
+
+// ========== 병렬 계산 ==========
+
+/// 명령 묶음의 요약: 결과를 진입 위치에 대한 함수로 표현
+///
+/// 회전은 평행이동에 대해 불변이므로, 진입 위치를 0으로 두고 계산한
+/// 상대 히스토그램에서 절대 위치 0에 해당하는 칸을 읽으면 됨.
+/// 바로 이동 명령(=, RESET) 이후는 진입 위치와 무관하므로 상수로 저장.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    size: usize,
+    /// 첫 바로 이동 명령 전까지 오른쪽으로 이동한 칸 수
+    offset: usize,
+    /// 진입 위치 p일 때 멈춘 횟수는 landings[(size - p) % size]
+    landings: Vec<u64>,
+    /// 진입 위치 p일 때 0을 거친 횟수는 passes[(size - p) % size]
+    passes: Vec<u64>,
+    /// 첫 바로 이동 명령부터 끝까지의 결과
+    tail: Option<Tail>,
+}
+
+/// 진입 위치와 무관한 뒷부분의 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Tail {
+    exit: usize,
+    landings: u64,
+    passes: u64,
+}
+
+impl Summary {
+    /// 빈 명령 묶음
+    pub fn identity(size: u64) -> Self {
+        let size = size as usize;
+        Summary {
+            size,
+            offset: 0,
+            landings: vec![0; size],
+            passes: vec![0; size],
+            tail: None,
+        }
+    }
+
+    /// 진입 위치에서 실행한 결과 (최종 위치, 0에 멈춘 횟수, 0을 거친 횟수)
+    pub fn apply(&self, position: u64) -> Result<(u64, u64, u64), OverflowError> {
+        let position = position as usize;
+        let zero = (self.size - position) % self.size;
+        let mut landings = self.landings[zero];
+        let mut passes = self.passes[zero];
+        let exit = match self.tail {
+            Some(tail) => {
+                landings = add(landings, tail.landings)?;
+                passes = add(passes, tail.passes)?;
+                tail.exit
+            }
+            None => (position + self.offset) % self.size,
+        };
+        Ok((exit as u64, landings, passes))
+    }
+
+    /// self 다음에 next를 실행한 요약, O(size)
+    pub fn then(&self, next: &Summary) -> Result<Summary, OverflowError> {
+        assert_eq!(
+            self.size, next.size,
+            "다이얼 크기가 다른 요약은 합칠 수 없음"
+        );
+
+        // 앞부분이 이미 진입 위치와 무관하면 next도 상수로 접힘
+        if let Some(tail) = self.tail {
+            let (exit, landings, passes) = next.apply(tail.exit as u64)?;
+            return Ok(Summary {
+                tail: Some(Tail {
+                    exit: exit as usize,
+                    landings: add(tail.landings, landings)?,
+                    passes: add(tail.passes, passes)?,
+                }),
+                ..self.clone()
+            });
+        }
+
+        // next의 진입 위치는 p + offset이므로 상대 좌표를 offset만큼 당겨서 더함
+        let n = self.size;
+        let merge = |mine: &[u64], theirs: &[u64]| -> Result<Vec<u64>, OverflowError> {
+            (0..n)
+                .map(|i| add(mine[i], theirs[(i + n - self.offset) % n]))
+                .collect()
+        };
+        Ok(Summary {
+            size: n,
+            offset: (self.offset + next.offset) % n,
+            landings: merge(&self.landings, &next.landings)?,
+            passes: merge(&self.passes, &next.passes)?,
+            tail: next.tail,
+        })
+    }
+}
+
+/// dial의 크기와 시작 위치(RESET 대상)로 명령 묶음을 요약
+/// O(명령 수 + size × 반복 명령의 서로 다른 step 수)
+pub fn summarize(dial: Dial, rotations: &[Rotation]) -> Result<Summary, OverflowError> {
+    let split = rotations
+        .iter()
+        .position(|r| r.as_turn().is_none())
+        .unwrap_or(rotations.len());
+    let (relative, absolute) = rotations.split_at(split);
+
+    let (hist, exit) = histogram_with_dial(Dial::new(dial.size(), 0), relative.iter().copied())?;
+    let tail = if absolute.is_empty() {
+        None
+    } else {
+        // 첫 명령이 바로 이동이므로 다이얼의 현재 위치는 결과에 영향 없음
+        let mut events = trace(
+            Dial::new(dial.size(), dial.start()),
+            absolute.iter().copied(),
+        );
+        let (mut landings, mut passes) = (0, 0);
+        for event in events.by_ref() {
+            let event = event?;
+            landings = add(landings, event.landings)?;
+            passes = add(passes, event.zero_hits)?;
+        }
+        Some(Tail {
+            exit: events.dial().position() as usize,
+            landings,
+            passes,
+        })
+    };
+
+    Ok(Summary {
+        size: dial.size() as usize,
+        offset: exit.position() as usize,
+        landings: hist.landings,
+        passes: hist.passes,
+        tail,
+    })
+}
+
+/// 명령을 threads개 묶음으로 나눠 병렬로 요약한 뒤 순서대로 합침
+pub fn summarize_parallel(
+    dial: Dial,
+    rotations: &[Rotation],
+    threads: usize,
+) -> Result<Summary, OverflowError> {
+    let chunk_len = rotations.len().div_ceil(threads.max(1)).max(1);
+    let summaries: Vec<Result<Summary, OverflowError>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = rotations
+            .chunks(chunk_len)
+            .map(|chunk| scope.spawn(move || summarize(dial, chunk)))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    summaries
+        .into_iter()
+        .try_fold(Summary::identity(dial.size()), |acc, s| acc.then(&s?))
+}
+
+/// (최종 위치, 0에 멈춘 횟수, 0을 거친 횟수)
+fn evaluate_parallel(
+    dial: Dial,
+    rotations: &[Rotation],
+    threads: usize,
+) -> Result<(u64, u64, u64), OverflowError> {
+    summarize_parallel(dial, rotations, threads)?.apply(dial.position())
+}
+
+/// Part 1 병렬 버전: password_part1_with와 같은 결과
+pub fn password_part1_parallel(
+    dial: Dial,
+    rotations: &[Rotation],
+    threads: usize,
+) -> Result<(u64, u64), OverflowError> {
+    let (position, landings, _) = evaluate_parallel(dial, rotations, threads)?;
+    Ok((position, landings))
+}
+
+/// Part 2 병렬 버전: password_part2_with와 같은 결과
+pub fn password_part2_parallel(
+    dial: Dial,
+    rotations: &[Rotation],
+    threads: usize,
+) -> Result<(u64, u64), OverflowError> {
+    let (position, _, passes) = evaluate_parallel(dial, rotations, threads)?;
+    Ok((position, passes))
+}
+
+// ========== 여러 다이얼 자물쇠 ==========
+
+/// 다이얼 번호가 붙은 명령: `2:L15`, 번호가 없으면 0번 다이얼
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockCommand {
+    pub dial: usize,
+    pub rotation: Rotation,
+}
+
+impl fmt::Display for LockCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.dial, self.rotation)
+    }
+}
+
+/// 한 줄을 파싱, 실패하면 (열 번호, 이유) 반환
+///
+/// 문법: `[<번호>:]<명령>`, 명령 부분은 parse_line과 같음
+fn parse_lock_line(line: &str) -> Result<LockCommand, (usize, ParseErrorKind)> {
+    if !line.starts_with(|c: char| c.is_ascii_digit()) {
+        return parse_line(line).map(|rotation| LockCommand { dial: 0, rotation });
+    }
+
+    let mut cursor = Cursor {
+        chars: line.chars().peekable(),
+        column: 1,
+    };
+    let dial = cursor.number()?;
+    let dial = usize::try_from(dial).map_err(|_| (1, ParseErrorKind::AmountTooLarge))?;
+    if cursor.chars.next_if_eq(&':').is_none() {
+        return Err((cursor.column, ParseErrorKind::MissingColon));
+    }
+
+    // 번호와 ':'는 ASCII라 열 번호가 곧 나머지의 바이트 위치
+    let offset = cursor.column;
+    let rotation = parse_line(&line[offset..]).map_err(|(column, kind)| (column + offset, kind))?;
+    Ok(LockCommand { dial, rotation })
+}
+
+/// 한 줄짜리 입력으로 파싱 (에러의 줄 번호는 1)
+impl FromStr for LockCommand {
+    type Err = ParseRotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_lock_line(s).map_err(|(column, kind)| ParseRotationError {
+            line: 1,
+            column,
+            kind,
+        })
+    }
+}
+
+/// 서로 독립적으로 도는 여러 다이얼
+/// 모든 다이얼이 동시에 0에 있으면 열림
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CombinationLock {
+    dials: Vec<Dial>,
+}
+
+impl CombinationLock {
+    /// 다이얼마다 크기와 시작 위치가 달라도 됨
+    pub fn new(dials: Vec<Dial>) -> Self {
+        assert!(!dials.is_empty(), "다이얼이 하나 이상 필요");
+        CombinationLock { dials }
+    }
+
+    pub fn dials(&self) -> &[Dial] {
+        &self.dials
+    }
+
+    /// 모든 다이얼이 0에 있는지
+    pub fn is_unlocked(&self) -> bool {
+        self.dials.iter().all(|dial| dial.position() == 0)
+    }
+
+    /// 명령을 해당 번호의 다이얼에 적용
+    pub fn apply(&mut self, command: LockCommand) -> Result<LockEvent, DialError> {
+        let dials = self.dials.len();
+        let dial = self
+            .dials
+            .get_mut(command.dial)
+            .ok_or(DialError::NoSuchDial {
+                index: command.dial,
+                dials,
+            })?;
+        let event = dial.apply(command.rotation)?;
+        // 다른 다이얼이 모두 0이면 움직인 다이얼이 0에 멈출 때마다 열림
+        let others_at_zero = self
+            .dials
+            .iter()
+            .enumerate()
+            .all(|(i, dial)| i == command.dial || dial.position() == 0);
+        Ok(LockEvent {
+            dial: command.dial,
+            unlocks: if others_at_zero { event.landings } else { 0 },
+            event,
+            unlocked: self.is_unlocked(),
+        })
+    }
+}
+
+/// 자물쇠 명령 하나를 실행한 기록
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockEvent {
+    /// 움직인 다이얼 번호
+    pub dial: usize,
+    pub event: DialEvent,
+    /// 명령 후 모든 다이얼이 0에 있음
+    pub unlocked: bool,
+    /// 명령 중 모든 다이얼이 동시에 0에 있었던 횟수 (반복 명령은 멈출 때마다 셈)
+    pub unlocks: u64,
+}
+
+/// 다이얼별 0에 멈춘 횟수와 0을 거친 횟수, 자물쇠가 열린 횟수
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockStats {
+    /// 다이얼별 마지막 위치
+    pub positions: Vec<u64>,
+    pub landings: Vec<u64>,
+    pub zero_hits: Vec<u64>,
+    /// 모든 다이얼이 동시에 0에 있었던 횟수 (반복 명령 중간에 멈춘 것도 셈)
+    pub unlocks: u64,
+}
+
+impl LockStats {
+    /// 아무 명령도 실행하지 않은 자물쇠의 집계
+    pub fn new(lock: &CombinationLock) -> Self {
+        let dials = lock.dials().len();
+        LockStats {
+            positions: lock.dials().iter().map(Dial::position).collect(),
+            landings: vec![0; dials],
+            zero_hits: vec![0; dials],
+            unlocks: 0,
+        }
+    }
+
+    /// 명령 하나의 기록을 더함
+    pub fn record(&mut self, event: &LockEvent) -> Result<(), OverflowError> {
+        let i = event.dial;
+        self.positions[i] = event.event.to;
+        self.landings[i] = add(self.landings[i], event.event.landings)?;
+        self.zero_hits[i] = add(self.zero_hits[i], event.event.zero_hits)?;
+        self.unlocks = add(self.unlocks, event.unlocks)?;
+        Ok(())
+    }
+
+    /// 모든 다이얼의 0에 멈춘 횟수 합
+    pub fn total_landings(&self) -> Result<u64, OverflowError> {
+        self.landings.iter().try_fold(0, |sum, &n| add(sum, n))
+    }
+
+    /// 모든 다이얼의 0을 거친 횟수 합
+    pub fn total_zero_hits(&self) -> Result<u64, OverflowError> {
+        self.zero_hits.iter().try_fold(0, |sum, &n| add(sum, n))
+    }
+}
+
+/// 자물쇠에 명령을 차례로 적용하고 집계
+pub fn lock_stats<I: IntoIterator<Item = LockCommand>>(
+    mut lock: CombinationLock,
+    commands: I,
+) -> Result<LockStats, DialError> {
+    let mut stats = LockStats::new(&lock);
+    for command in commands {
+        stats.record(&lock.apply(command)?)?;
+    }
+    Ok(stats)
+}
+
+// ========== 시작 위치 찾기 ==========
+
+/// 시작 위치를 모르는 로그를 모든 시작 위치에 대해 한 번에 계산한 결과
+///
+/// 시작 위치 s에서 다이얼 위치는 s + (상대 이동량)이므로, 시작 0 기준의
+/// 상대 위치 히스토그램에서 q = (size - s) % size 칸이 s의 0이 됨.
+/// `=n` 이후 구간은 s와 관계없는 상수, `RESET`은 다시 s(상대 위치 0)로 돌아감.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StartScan {
+    /// 상대 위치별 멈춘 횟수와 지나간 횟수
+    relative: Histogram,
+    /// `=n` 이후 구간에서 0에 멈춘 횟수와 거친 횟수
+    landings: u64,
+    zero_hits: u64,
+    /// 마지막 위치: Ok면 상대 이동량, Err면 시작 위치와 관계없는 고정 위치
+    end: Result<u64, u64>,
+}
+
+impl StartScan {
+    pub fn size(&self) -> u64 {
+        self.relative.landings.len() as u64
+    }
+
+    /// 시작 위치 하나의 (마지막 위치, 0에 멈춘 횟수, 0을 거친 횟수)
+    pub fn evaluate(&self, start: u64) -> Result<(u64, u64, u64), OverflowError> {
+        let size = self.size();
+        assert!(
+            start < size,
+            "시작 위치가 범위를 벗어남: {} (크기 {})",
+            start,
+            size
+        );
+        let q = ((size - start) % size) as usize;
+        let position = match self.end {
+            Ok(offset) => (start + offset) % size,
+            Err(position) => position,
+        };
+        Ok((
+            position,
+            add(self.relative.landings[q], self.landings)?,
+            add(self.relative.passes[q], self.zero_hits)?,
+        ))
+    }
+
+    /// Part 1 횟수가 가장 큰 시작 위치와 그 횟수, 같으면 작은 위치
+    pub fn best_start_part1(&self) -> Result<(u64, u64), OverflowError> {
+        self.best_start(|(_, landings, _)| landings)
+    }
+
+    /// Part 2 횟수가 가장 큰 시작 위치와 그 횟수, 같으면 작은 위치
+    pub fn best_start_part2(&self) -> Result<(u64, u64), OverflowError> {
+        self.best_start(|(_, _, zero_hits)| zero_hits)
+    }
+
+    /// Part 1 횟수가 count인 시작 위치들 (오름차순)
+    pub fn starts_matching_part1(&self, count: u64) -> Result<Vec<u64>, OverflowError> {
+        self.starts_matching(count, |(_, landings, _)| landings)
+    }
+
+    /// Part 2 횟수가 count인 시작 위치들 (오름차순)
+    pub fn starts_matching_part2(&self, count: u64) -> Result<Vec<u64>, OverflowError> {
+        self.starts_matching(count, |(_, _, zero_hits)| zero_hits)
+    }
+
+    fn best_start(&self, count: fn((u64, u64, u64)) -> u64) -> Result<(u64, u64), OverflowError> {
+        let mut best = (0, count(self.evaluate(0)?));
+        for start in 1..self.size() {
+            let n = count(self.evaluate(start)?);
+            if n > best.1 {
+                best = (start, n);
+            }
+        }
+        Ok(best)
+    }
+
+    fn starts_matching(
+        &self,
+        target: u64,
+        count: fn((u64, u64, u64)) -> u64,
+    ) -> Result<Vec<u64>, OverflowError> {
+        let mut starts = Vec::new();
+        for start in 0..self.size() {
+            if count(self.evaluate(start)?) == target {
+                starts.push(start);
+            }
+        }
+        Ok(starts)
+    }
+}
+
+/// 크기 size인 다이얼의 모든 시작 위치를 명령 한 번 훑어서 계산
+///
+/// 시작 위치마다 다시 시뮬레이션하지 않고, 시작 0 기준의 상대 위치를
+/// histogram과 같은 차분 배열로 모음: O(명령 수 + size × 반복 명령의 서로 다른 step 수)
+pub fn scan_starts<I: IntoIterator<Item = Rotation>>(
+    size: u64,
+    rotations: I,
+) -> Result<StartScan, OverflowError> {
+    // `=n` 이후 `RESET` 전까지는 실제 위치를 아는 다이얼로 계산
+    let mut fixed: Option<Dial> = None;
+    let (mut landings, mut zero_hits) = (0u64, 0u64);
+    let mut error = None;
+
+    let relative_rotations = rotations
+        .into_iter()
+        .map_while(|rotation| {
+            let dial = match (rotation, &mut fixed) {
+                (Rotation::Reset, _) => {
+                    fixed = None;
+                    return Some(Some(rotation));
+                }
+                (Rotation::Set(_), None) => fixed.insert(Dial::new(size, 0)),
+                (_, None) => return Some(Some(rotation)),
+                (_, Some(dial)) => dial,
+            };
+            let result = dial.apply(rotation).and_then(|event| {
+                landings = add(landings, event.landings)?;
+                zero_hits = add(zero_hits, event.zero_hits)?;
+                Ok(())
+            });
+            match result {
+                Ok(()) => Some(None),
+                Err(e) => {
+                    error = Some(e);
+                    None
+                }
+            }
+        })
+        .flatten();
+    let (relative, dial) = histogram_with_dial(Dial::new(size, 0), relative_rotations)?;
+
+    if let Some(error) = error {
+        return Err(error);
+    }
+    Ok(StartScan {
+        relative,
+        landings,
+        zero_hits,
+        end: match fixed {
+            Some(dial) => Err(dial.position()),
+            None => Ok(dial.position()),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_lines() -> Vec<String> {
+        [
+            "L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
+
+    fn test_commands() -> Vec<Rotation> {
+        commands(&[
+            "L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82",
+        ])
+    }
+
+    fn commands(list: &[&str]) -> Vec<Rotation> {
+        list.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_password_part1() {
+        assert_eq!(password_part1(test_lines()).unwrap(), (32, 3));
+    }
+
+    #[test]
+    fn test_password_part2() {
+        assert_eq!(password_part2(test_lines()).unwrap(), (32, 6));
+    }
+
+    #[test]
+    fn test_default_dial() {
+        assert_eq!(Dial::default(), Dial::new(100, 50));
+        assert_eq!(
+            password_part2_with(Dial::new(100, 50), test_commands()),
+            Ok((32, 6))
+        );
+    }
+
+    #[test]
+    fn test_dial_size_10() {
+        // 3 -R7-> 0 -L10-> 0 -L3-> 7 -R25-> 2
+        let cmds = commands(&["R7", "L10", "L3", "R25"]);
+        assert_eq!(
+            password_part1_with(Dial::new(10, 3), cmds.clone()),
+            Ok((2, 2))
+        );
+        // R7: 0 도착(1), L10: 한 바퀴(1), L3: 0에서 출발(0), R25: 10/20/30 통과(3)
+        assert_eq!(password_part2_with(Dial::new(10, 3), cmds), Ok((2, 5)));
+    }
+
+    #[test]
+    fn test_dial_size_60() {
+        // 0 -L60-> 0 -L59-> 1 -L1-> 0 -R120-> 0
+        let cmds = commands(&["L60", "L59", "L1", "R120"]);
+        assert_eq!(
+            password_part1_with(Dial::new(60, 0), cmds.clone()),
+            Ok((0, 3))
+        );
+        assert_eq!(password_part2_with(Dial::new(60, 0), cmds), Ok((0, 4)));
+    }
+
+    #[test]
+    fn test_dial_size_360() {
+        // 359 -R1-> 0 -L361-> 359 -R721-> 0
+        let cmds = commands(&["R1", "L361", "R721"]);
+        assert_eq!(
+            password_part1_with(Dial::new(360, 359), cmds.clone()),
+            Ok((0, 2))
+        );
+        // R1: 도착(1), L361: 0에서 출발해 한 바퀴(1), R721: 359에서 출발해 세 번(3)
+        assert_eq!(password_part2_with(Dial::new(360, 359), cmds), Ok((0, 5)));
+    }
+
+    #[test]
+    fn test_trace_events() {
+        let events: Vec<DialEvent> = trace(Dial::default(), test_commands())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(events.len(), 10);
+
+        // 50 -L68-> 82: 0을 한 번 지나감
+        assert_eq!(
+            events[0],
+            DialEvent {
+                rotation: Rotation::Left(68),
+                from: 50,
+                to: 82,
+                landings: 0,
+                zero_hits: 1,
+            }
+        );
+        // 52 -R48-> 0: 0에 도착
+        assert_eq!(
+            (events[2].from, events[2].to, events[2].zero_hits),
+            (52, 0, 1)
+        );
+        // 0 -L5-> 95: 0에서 출발한 것은 세지 않음
+        assert_eq!(
+            (events[3].from, events[3].to, events[3].zero_hits),
+            (0, 95, 0)
+        );
+
+        // 이벤트는 앞 이벤트의 도착 위치에서 이어짐
+        for pair in events.windows(2) {
+            assert_eq!(pair[0].to, pair[1].from);
+        }
+    }
+
+    #[test]
+    fn test_trace_dial_state() {
+        let mut events = trace(Dial::new(10, 3), commands(&["R7", "L3"]));
+        assert_eq!(events.dial().position(), 3);
+        events.next();
+        assert_eq!(events.dial().position(), 0);
+        events.next();
+        assert_eq!(events.dial().position(), 7);
+        assert_eq!(events.next(), None);
+        // 넘친 명령은 에러 이벤트가 되고 다이얼은 그대로
+        let mut events = trace(Dial::new(1, 0), commands(&["R2x18446744073709551615"]));
+        assert_eq!(events.next(), Some(Err(OverflowError)));
+        assert_eq!(events.dial().position(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dial_start_out_of_range() {
+        Dial::new(10, 10);
+    }
+
+    #[test]
+    fn test_parse_rotations() {
+        assert_eq!(
+            parse_rotations("L68\nR48\nL0"),
+            Ok(vec![
+                Rotation::Left(68),
+                Rotation::Right(48),
+                Rotation::Left(0)
+            ])
+        );
+        assert_eq!(
+            "R18446744073709551615".parse(),
+            Ok(Rotation::Right(u64::MAX))
+        );
+        assert_eq!(Rotation::Left(68).to_string(), "L68");
+    }
+
+    #[test]
+    fn test_parse_rotations_reports_every_error() {
+        let input = "R10\n\nr5\nL\nL1y\nR18446744073709551616\n→3\nR7";
+        let errors = parse_rotations(input).unwrap_err();
+        let summary: Vec<(usize, usize, ParseErrorKind)> = errors
+            .into_iter()
+            .map(|e| (e.line, e.column, e.kind))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (2, 1, ParseErrorKind::Empty),
+                (3, 1, ParseErrorKind::InvalidDirection('r')),
+                (4, 2, ParseErrorKind::MissingAmount),
+                (5, 3, ParseErrorKind::InvalidDigit('y')),
+                (6, 2, ParseErrorKind::AmountTooLarge),
+                (7, 1, ParseErrorKind::InvalidDirection('→')),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_error_column_counts_chars() {
+        // 멀티바이트 문자도 한 열로 셈
+        let err = "R1é2".parse::<Rotation>().unwrap_err();
+        assert_eq!((err.line, err.column), (1, 3));
+        assert_eq!(err.kind, ParseErrorKind::InvalidDigit('é'));
+        assert_eq!(err.to_string(), "1:3: 숫자가 아닌 문자: 'é'");
+    }
+
+    #[test]
+    fn test_parse_extended_commands() {
+        assert_eq!(
+            parse_rotations("=37\nR48x3\nRESET\nL5x1"),
+            Ok(vec![
+                Rotation::Set(37),
+                Rotation::Repeat {
+                    direction: Direction::Right,
+                    amount: 48,
+                    times: 3
+                },
+                Rotation::Reset,
+                Rotation::Repeat {
+                    direction: Direction::Left,
+                    amount: 5,
+                    times: 1
+                },
+            ])
+        );
+        for text in ["=37", "R48x3", "RESET", "L0x0"] {
+            assert_eq!(text.parse::<Rotation>().unwrap().to_string(), text);
+        }
+
+        let errors: Vec<(usize, ParseErrorKind)> = ["=", "=3a", "R1x", "R1xx", "Lx2", "RESETS"]
+            .iter()
+            .map(|s| {
+                let e = s.parse::<Rotation>().unwrap_err();
+                (e.column, e.kind)
+            })
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                (2, ParseErrorKind::MissingAmount),
+                (3, ParseErrorKind::InvalidDigit('a')),
+                (4, ParseErrorKind::MissingAmount),
+                (4, ParseErrorKind::InvalidDigit('x')),
+                (2, ParseErrorKind::InvalidDigit('x')),
+                (2, ParseErrorKind::InvalidDigit('E')),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repeat_matches_expanded_rotations() {
+        for size in [1, 7, 10, 12, 100] {
+            for start in 0..size {
+                for amount in [0, 1, 3, 6, 10, 25, 100, 143] {
+                    for times in [0, 1, 2, 5, 13, 40] {
+                        for direction in [Direction::Left, Direction::Right] {
+                            let single = match direction {
+                                Direction::Left => Rotation::Left(amount),
+                                Direction::Right => Rotation::Right(amount),
+                            };
+                            let expanded = vec![single; times as usize];
+                            let repeated = Rotation::Repeat {
+                                direction,
+                                amount,
+                                times,
+                            };
+
+                            let dial = Dial::new(size, start);
+                            assert_eq!(
+                                password_part1_with(dial, [repeated]),
+                                password_part1_with(dial, expanded.clone()),
+                                "Part1 불일치: {} (크기 {}, 시작 {})",
+                                repeated,
+                                size,
+                                start
+                            );
+                            assert_eq!(
+                                password_part2_with(dial, [repeated]),
+                                password_part2_with(dial, expanded),
+                                "Part2 불일치: {} (크기 {}, 시작 {})",
+                                repeated,
+                                size,
+                                start
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_huge_repeat_is_closed_form() {
+        let rotation: Rotation = "R1x1000000000".parse().unwrap();
+        // 10억 번 한 칸씩: 50에서 출발해 0에 1000만 번 도착
+        assert_eq!(password_part1(["R1x1000000000"]).unwrap(), (50, 10_000_000));
+        assert_eq!(
+            password_part2_with(Dial::default(), [rotation]),
+            Ok((50, 10_000_000))
+        );
+
+        let rotation: Rotation = "L7x1000000000".parse().unwrap();
+        // 7과 100은 서로소이므로 100번마다 한 번 0에 멈춤
+        assert_eq!(
+            password_part1_with(Dial::default(), [rotation]),
+            Ok((50, 10_000_000))
+        );
+        assert_eq!(password_part2(["L7x1000000000"]).unwrap(), (50, 70_000_000));
+    }
+
+    #[test]
+    fn test_set_and_reset() {
+        let dial = Dial::new(10, 3);
+        let cmds = commands(&["=0", "R4", "=17", "RESET", "L3", "=0", "RESET"]);
+        let positions: Vec<u64> = trace(dial, cmds.clone()).map(|e| e.unwrap().to).collect();
+        assert_eq!(positions, vec![0, 4, 7, 3, 0, 0, 3]);
+
+        // 0으로 바로 이동하면 멈춘 것이자 거친 것, 이미 0이어도 셈
+        assert_eq!(password_part1_with(dial, cmds.clone()), Ok((3, 3)));
+        assert_eq!(password_part2_with(dial, cmds), Ok((3, 3)));
+    }
+
+    #[test]
+    fn test_histogram_zero_matches_passwords() {
+        let hist = histogram(Dial::default(), test_commands()).unwrap();
+        assert_eq!(hist.landings.len(), 100);
+        assert_eq!(hist.landings[0], 3);
+        assert_eq!(hist.passes[0], 6);
+
+        let cmds = commands(&["R7", "=0", "L10x3", "L3", "RESET", "R25x4", "=9", "L1"]);
+        let dial = Dial::new(10, 3);
+        let hist = histogram(dial, cmds.clone()).unwrap();
+        assert_eq!(
+            hist.landings[0],
+            password_part1_with(dial, cmds.clone()).unwrap().1
+        );
+        assert_eq!(hist.passes[0], password_part2_with(dial, cmds).unwrap().1);
+    }
+
+    #[test]
+    fn test_histogram_matches_unit_steps() {
+        // 한 칸 회전으로 풀어 쓰면 지나간 위치 = 멈춘 위치
+        let cmds = commands(&[
+            "R7", "=0", "L10x3", "L3", "RESET", "R25x4", "=9", "L1", "R6x5", "L4x7", "R0x3",
+        ]);
+        let mut unit_steps = Vec::new();
+        for rotation in &cmds {
+            match rotation.as_turn() {
+                Some((direction, amount, times)) => {
+                    let step = match direction {
+                        Direction::Left => Rotation::Left(1),
+                        Direction::Right => Rotation::Right(1),
+                    };
+                    unit_steps.extend(std::iter::repeat_n(step, (amount * times) as usize));
+                }
+                None => unit_steps.push(*rotation),
+            }
+        }
+
+        for size in [1, 4, 10, 12] {
+            for start in 0..size {
+                let dial = Dial::new(size, start);
+                let hist = histogram(dial, cmds.clone()).unwrap();
+                let unit = histogram(dial, unit_steps.clone()).unwrap();
+                assert_eq!(hist.passes, unit.landings, "크기 {}, 시작 {}", size, start);
+                assert_eq!(
+                    hist.landings.iter().sum::<u64>(),
+                    cmds.iter()
+                        .map(|r| r.as_turn().map_or(1, |(_, _, times)| times))
+                        .sum::<u64>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_histogram_repeat_landings() {
+        // 10칸, 3에서 R4 x 7: 7, 1, 5, 9, 3, 7, 1
+        let hist = histogram(Dial::new(10, 3), commands(&["R4x7"])).unwrap();
+        assert_eq!(hist.landings, vec![0, 2, 0, 1, 0, 1, 0, 2, 0, 1]);
+    }
+
+    #[test]
+    fn test_histogram_repeat_landings_match_expanded() {
+        // 같은 step의 반복 명령이 여러 출발 위치에서 쌓여도 펼친 명령과 같음
+        let mut rng = Rng(0x5DEE_CE66_D1CE_4E5B);
+        for size in [1, 6, 10, 12, 36] {
+            for _ in 0..50 {
+                let cmds = random_rotations(&mut rng, 30, size * 3);
+                let expanded: Vec<Rotation> = cmds
+                    .iter()
+                    .flat_map(|rotation| match rotation.as_turn() {
+                        Some((direction, amount, times)) => {
+                            let single = match direction {
+                                Direction::Left => Rotation::Left(amount),
+                                Direction::Right => Rotation::Right(amount),
+                            };
+                            vec![single; times as usize]
+                        }
+                        None => vec![*rotation],
+                    })
+                    .collect();
+                let dial = Dial::new(size, rng.below(size));
+                assert_eq!(
+                    histogram(dial, cmds).unwrap().landings,
+                    histogram(dial, expanded).unwrap().landings,
+                    "크기 {}",
+                    size
+                );
+            }
+        }
+    }
+
+    /// 테스트용 xorshift 난수
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, n: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % n
+        }
+    }
+
+    fn random_rotations(rng: &mut Rng, len: usize, max_amount: u64) -> Vec<Rotation> {
+        (0..len)
+            .map(|_| {
+                let amount = rng.below(max_amount + 1);
+                match rng.below(10) {
+                    0 => Rotation::Set(amount),
+                    1 => Rotation::Reset,
+                    2 | 3 => Rotation::Repeat {
+                        direction: if rng.below(2) == 0 {
+                            Direction::Left
+                        } else {
+                            Direction::Right
+                        },
+                        amount,
+                        times: rng.below(20),
+                    },
+                    4..=6 => Rotation::Left(amount),
+                    _ => Rotation::Right(amount),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parallel_sample() {
+        let cmds = test_commands();
+        for threads in 1..=12 {
+            assert_eq!(
+                password_part1_parallel(Dial::default(), &cmds, threads),
+                Ok((32, 3))
+            );
+            assert_eq!(
+                password_part2_parallel(Dial::default(), &cmds, threads),
+                Ok((32, 6))
+            );
+        }
+        assert_eq!(
+            password_part2_parallel(Dial::default(), &[], 4),
+            Ok((50, 0))
+        );
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let mut rng = Rng(0x2025_1201);
+        for round in 0..200 {
+            let size = rng.below(40) + 1;
+            let dial = Dial::new(size, rng.below(size));
+            let len = rng.below(60) as usize;
+            let cmds = random_rotations(&mut rng, len, 3 * size);
+            let threads = rng.below(8) as usize + 1;
+
+            assert_eq!(
+                password_part1_parallel(dial, &cmds, threads),
+                password_part1_with(dial, cmds.clone()),
+                "Part1 불일치 (round {})",
+                round
+            );
+            assert_eq!(
+                password_part2_parallel(dial, &cmds, threads),
+                password_part2_with(dial, cmds.clone()),
+                "Part2 불일치 (round {})",
+                round
+            );
+        }
+    }
+
+    #[test]
+    fn test_summary_composition_is_associative() {
+        let mut rng = Rng(42);
+        let dial = Dial::new(12, 5);
+        let parts: Vec<Summary> = (0..3)
+            .map(|_| summarize(dial, &random_rotations(&mut rng, 15, 40)).unwrap())
+            .collect();
+        let left = parts[0].then(&parts[1]).unwrap().then(&parts[2]).unwrap();
+        let right = parts[0].then(&parts[1].then(&parts[2]).unwrap()).unwrap();
+        for position in 0..12 {
+            assert_eq!(left.apply(position), right.apply(position));
+        }
+    }
+
+    #[test]
+    fn test_password_from_reader() {
+        let input = "L68\r\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+        assert_eq!(password_part1_reader(input.as_bytes()).unwrap(), (32, 3));
+        assert_eq!(password_part2_reader(input.as_bytes()).unwrap(), (32, 6));
+
+        // 줄 끝 개행이 없어도 마지막 줄을 읽음
+        assert_eq!(
+            password_part2_reader("L50\nR100".as_bytes()).unwrap(),
+            (0, 2)
+        );
+    }
+
+    #[test]
+    fn test_streaming_errors_keep_line_numbers() {
+        let err = password_part1(["R1", "R2", "X3", "R4y"]).unwrap_err();
+        assert!(matches!(err, DialError::Parse(e) if (e.line, e.column) == (3, 1)));
+
+        let results: Vec<_> = read_rotations("R1\n\nR2z\r\nL3\n".as_bytes()).collect();
+        assert_eq!(results.len(), 4);
+        assert!(matches!(results[0], Ok(Rotation::Right(1))));
+        assert!(matches!(
+            &results[1],
+            Err(DialError::Parse(e)) if e.line == 2 && e.kind == ParseErrorKind::Empty
+        ));
+        assert!(matches!(
+            &results[2],
+            Err(DialError::Parse(e)) if (e.line, e.column) == (3, 3)
+        ));
+        assert!(matches!(results[3], Ok(Rotation::Left(3))));
+    }
+
+    #[test]
+    fn test_trillion_amounts() {
+        // i32로는 넘치던 회전량: 50에서 R5조 → 0을 5e10번 지남
+        let cmds = commands(&["R5000000000000", "L5000000000050"]);
+        assert_eq!(
+            password_part2_with(Dial::default(), cmds.clone()),
+            Ok((0, 100_000_000_001))
+        );
+        assert_eq!(
+            password_part2_parallel(Dial::default(), &cmds, 2),
+            Ok((0, 100_000_000_001))
+        );
+        assert_eq!(
+            histogram(Dial::new(360, 0), cmds).unwrap().passes[0],
+            password_part2_with(
+                Dial::new(360, 0),
+                commands(&["R5000000000000", "L5000000000050"])
+            )
+            .unwrap()
+            .1
+        );
+    }
+
+    #[test]
+    fn test_overflow_is_reported() {
+        // 한 명령의 횟수가 u64를 넘음
+        let huge = commands(&["R18446744073709551615x18446744073709551615"]);
+        assert_eq!(
+            password_part2_with(Dial::default(), huge.clone()),
+            Err(OverflowError)
+        );
+        assert_eq!(
+            password_part2_parallel(Dial::default(), &huge, 2),
+            Err(OverflowError)
+        );
+        assert_eq!(histogram(Dial::default(), huge), Err(OverflowError));
+        // 멈춘 횟수는 반복 횟수를 넘지 않으므로 Part 1은 계산됨
+        assert!(password_part1(["R100x18446744073709551615"]).is_ok());
+
+        // 각 명령은 괜찮지만 합계가 u64를 넘음
+        let cmds = commands(&["R18446744073709551615", "R1"]);
+        assert_eq!(
+            password_part2_with(Dial::new(1, 0), cmds.clone()),
+            Err(OverflowError)
+        );
+        assert_eq!(
+            password_part2_parallel(Dial::new(1, 0), &cmds, 2),
+            Err(OverflowError)
+        );
+        let input = "R18446744073709551615x100\nR18446744073709551615x100\n";
+        assert_eq!(
+            password_part2_reader(input.lines().next().unwrap().as_bytes()).unwrap(),
+            (50, u64::MAX)
+        );
+        assert!(matches!(
+            password_part2_reader(input.as_bytes()),
+            Err(DialError::Overflow(OverflowError))
+        ));
+    }
+
+    /// 경계값 위주의 회전량: 0, size의 배수, 배수 ± 1, 작은 임의 값
+    fn edge_amount(rng: &mut Rng, size: u64) -> u64 {
+        let laps = rng.below(4);
+        match rng.below(5) {
+            0 => 0,
+            1 => laps * size,
+            2 => laps * size + 1,
+            3 => (laps * size).saturating_sub(1),
+            _ => rng.below(3 * size + 1),
+        }
+    }
+
+    fn edge_rotations(rng: &mut Rng, size: u64, len: usize) -> Vec<Rotation> {
+        (0..len)
+            .map(|_| {
+                let amount = edge_amount(rng, size);
+                let direction = if rng.below(2) == 0 {
+                    Direction::Left
+                } else {
+                    Direction::Right
+                };
+                match rng.below(12) {
+                    0 => Rotation::Set(if rng.below(2) == 0 { 0 } else { amount }),
+                    1 => Rotation::Reset,
+                    2..=4 => Rotation::Repeat {
+                        direction,
+                        amount,
+                        times: rng.below(12),
+                    },
+                    _ if direction == Direction::Left => Rotation::Left(amount),
+                    _ => Rotation::Right(amount),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_simulate_clicks_sample() {
+        assert_eq!(
+            simulate_clicks(Dial::default(), test_commands()),
+            (32, 3, 6)
+        );
+    }
+
+    #[test]
+    fn test_closed_form_matches_clicks() {
+        let mut rng = Rng(0x0d1a_1c0d);
+        for round in 0..5000 {
+            let size = match rng.below(4) {
+                0 => 100,
+                1 => 1,
+                _ => rng.below(30) + 1,
+            };
+            // 0에서 시작하는 경우를 자주 만듦
+            let start = if rng.below(3) == 0 {
+                0
+            } else {
+                rng.below(size)
+            };
+            let dial = Dial::new(size, start);
+            let len = rng.below(25) as usize;
+            let cmds = edge_rotations(&mut rng, size, len);
+
+            let (position, landings, passes) = simulate_clicks(dial, cmds.clone());
+            let context = format!("round {}, 크기 {}, 시작 {}, {:?}", round, size, start, cmds);
+            assert_eq!(
+                password_part1_with(dial, cmds.clone()),
+                Ok((position, landings)),
+                "Part1 불일치: {}",
+                context
+            );
+            assert_eq!(
+                password_part2_with(dial, cmds.clone()),
+                Ok((position, passes)),
+                "Part2 불일치: {}",
+                context
+            );
+
+            if round % 10 == 0 {
+                let hist = histogram(dial, cmds.clone()).unwrap();
+                assert_eq!(
+                    (hist.landings[0], hist.passes[0]),
+                    (landings, passes),
+                    "{}",
+                    context
+                );
+                let threads = rng.below(4) as usize + 1;
+                assert_eq!(
+                    password_part2_parallel(dial, &cmds, threads),
+                    Ok((position, passes)),
+                    "병렬 불일치: {}",
+                    context
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_closed_form_left_turn_from_zero() {
+        // 0에서 왼쪽으로 size의 배수만큼: 출발은 세지 않고 도착만 셈
+        for size in [1, 2, 7, 100] {
+            for laps in 0..4 {
+                let cmds = vec![Rotation::Left(laps * size)];
+                let dial = Dial::new(size, 0);
+                let (position, landings, passes) = simulate_clicks(dial, cmds.clone());
+                assert_eq!((position, passes), (0, laps));
+                assert_eq!(landings, 1);
+                assert_eq!(password_part2_with(dial, cmds), Ok((0, laps)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_lock_commands() {
+        let cmds = parse_lock_commands("2:L15\nR3\n10:R48x3\n1:RESET\n0:=7").unwrap();
+        let expected = [
+            (2, Rotation::Left(15)),
+            (0, Rotation::Right(3)),
+            (
+                10,
+                Rotation::Repeat {
+                    direction: Direction::Right,
+                    amount: 48,
+                    times: 3,
+                },
+            ),
+            (1, Rotation::Reset),
+            (0, Rotation::Set(7)),
+        ];
+        let expected: Vec<LockCommand> = expected
+            .into_iter()
+            .map(|(dial, rotation)| LockCommand { dial, rotation })
+            .collect();
+        assert_eq!(cmds, expected);
+        assert_eq!(expected[0].to_string(), "2:L15");
+
+        // 열 번호는 줄 전체 기준
+        let errors = parse_lock_commands("2L15\n12:Lx\n3:\nL5").unwrap_err();
+        let errors: Vec<_> = errors
+            .into_iter()
+            .map(|e| (e.line, e.column, e.kind))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                (1, 2, ParseErrorKind::MissingColon),
+                (2, 5, ParseErrorKind::InvalidDigit('x')),
+                (3, 3, ParseErrorKind::Empty),
+            ]
+        );
+
+        // 단일 다이얼 문법에는 번호가 없음
+        assert!(parse_rotations("2:L15").is_err());
+    }
+
+    #[test]
+    fn test_single_dial_lock_matches_passwords() {
+        let lock = CombinationLock::new(vec![Dial::default()]);
+        let cmds = lock_commands_from_lines(test_lines()).map(Result::unwrap);
+        let stats = lock_stats(lock, cmds).unwrap();
+        assert_eq!(stats.positions, vec![32]);
+        assert_eq!(stats.landings, vec![3]);
+        assert_eq!(stats.zero_hits, vec![6]);
+        // 0번 다이얼 하나뿐이면 0에 멈출 때마다 열림
+        assert_eq!(stats.unlocks, 3);
+    }
+
+    #[test]
+    fn test_lock_per_dial_and_unlock() {
+        let lock = CombinationLock::new(vec![Dial::new(10, 5), Dial::new(60, 0), Dial::default()]);
+        let cmds =
+            parse_lock_commands("L5\n1:R60\n2:R50\n1:R7\n0:R25\n2:L150\n1:L7\n2:=0\n0:RESET")
+                .unwrap();
+
+        let mut lock_trace = lock.clone();
+        let unlocked: Vec<bool> = cmds
+            .iter()
+            .map(|&cmd| lock_trace.apply(cmd).unwrap().unlocked)
+            .collect();
+        // 2:R50 후에만 (0, 0, 0), 2:=0 후에는 0번이 5에 있음
+        assert_eq!(
+            unlocked,
+            vec![false, false, true, false, false, false, false, false, false]
+        );
+        assert!(!lock_trace.is_unlocked());
+
+        let stats = lock_stats(lock, cmds).unwrap();
+        assert_eq!(stats.positions, vec![5, 0, 0]);
+        // 0번: L5 → 0 (멈춤 1, 거침 1), R25 → 5 (거침 2), RESET → 5
+        // 1번: R60 → 0 (멈춤 1, 거침 1), R7 → 7, L7 → 0 (멈춤 1, 거침 1)
+        // 2번: R50 → 0 (멈춤 1, 거침 1), L150 → 50 (거침 1), =0 (멈춤 1, 거침 1)
+        assert_eq!(stats.landings, vec![1, 2, 2]);
+        assert_eq!(stats.zero_hits, vec![3, 2, 3]);
+        assert_eq!(stats.total_landings(), Ok(5));
+        assert_eq!(stats.total_zero_hits(), Ok(8));
+        assert_eq!(stats.unlocks, 1);
+    }
+
+    #[test]
+    fn test_lock_repeat_unlocks_each_landing() {
+        // 50에서 R50 x 2: 0에 멈췄다가 50으로, 멈출 때마다 열림
+        let mut lock = CombinationLock::new(vec![Dial::default()]);
+        let event = lock.apply("R50x2".parse().unwrap()).unwrap();
+        assert_eq!((event.event.landings, event.unlocks), (1, 1));
+        assert!(!event.unlocked);
+
+        // 다른 다이얼이 0일 때만 셈: 0번이 0이 된 뒤 1번 R25 x 8은 0에 두 번 멈춤
+        let lock = CombinationLock::new(vec![Dial::default(); 2]);
+        let cmds = parse_lock_commands("1:R25x8\n0:R50\n1:R25x8").unwrap();
+        let stats = lock_stats(lock, cmds).unwrap();
+        assert_eq!(stats.landings, vec![1, 4]);
+        assert_eq!(stats.unlocks, 2);
+    }
+
+    #[test]
+    fn test_lock_unknown_dial() {
+        let mut lock = CombinationLock::new(vec![Dial::default(); 2]);
+        let command: LockCommand = "2:L15".parse().unwrap();
+        assert!(matches!(
+            lock.apply(command),
+            Err(DialError::NoSuchDial { index: 2, dials: 2 })
+        ));
+        assert_eq!(lock.dials(), &[Dial::default(); 2]);
+    }
+
+    #[test]
+    fn test_scan_starts_sample() {
+        let scan = scan_starts(100, test_commands()).unwrap();
+        assert_eq!(scan.evaluate(50), Ok((32, 3, 6)));
+        for start in [0, 7, 50, 99] {
+            let dial = Dial::new(100, start);
+            let (position, landings) = password_part1_with(dial, test_commands()).unwrap();
+            let (_, passes) = password_part2_with(dial, test_commands()).unwrap();
+            assert_eq!(scan.evaluate(start), Ok((position, landings, passes)));
+        }
+        let (start, landings) = scan.best_start_part1().unwrap();
+        assert_eq!(scan.evaluate(start).unwrap().1, landings);
+        assert!(scan.starts_matching_part1(3).unwrap().contains(&50));
+        assert!(scan.starts_matching_part2(6).unwrap().contains(&50));
+    }
+
+    #[test]
+    fn test_scan_starts_matches_every_start() {
+        let mut rng = Rng(0xC0FFEE);
+        for round in 0..300 {
+            let size = [1, 2, 5, 10, 12, 100][rng.below(6) as usize];
+            let len = rng.below(20) as usize;
+            let cmds = edge_rotations(&mut rng, size, len);
+            let scan = scan_starts(size, cmds.clone()).unwrap();
+
+            let mut expected = Vec::new();
+            for start in 0..size {
+                let dial = Dial::new(size, start);
+                let (position, landings, passes) = simulate_clicks(dial, cmds.clone());
+                assert_eq!(
+                    scan.evaluate(start),
+                    Ok((position, landings, passes)),
+                    "round {}, 크기 {}, 시작 {}, {:?}",
+                    round,
+                    size,
+                    start,
+                    cmds
+                );
+                expected.push((start, landings, passes));
+            }
+
+            let best1 = expected
+                .iter()
+                .map(|&(s, l, _)| (l, std::cmp::Reverse(s)))
+                .max();
+            let best2 = expected
+                .iter()
+                .map(|&(s, _, p)| (p, std::cmp::Reverse(s)))
+                .max();
+            let (l, std::cmp::Reverse(s)) = best1.unwrap();
+            assert_eq!(scan.best_start_part1(), Ok((s, l)));
+            let (p, std::cmp::Reverse(s)) = best2.unwrap();
+            assert_eq!(scan.best_start_part2(), Ok((s, p)));
+
+            let target = expected[0].2;
+            let matching: Vec<u64> = expected
+                .iter()
+                .filter(|&&(_, _, p)| p == target)
+                .map(|&(s, _, _)| s)
+                .collect();
+            assert_eq!(scan.starts_matching_part2(target), Ok(matching));
+        }
+    }
+}
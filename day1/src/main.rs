@@ -1,95 +1,250 @@
-fn password_part1(commands: Vec<String>) -> (i32, i32) {
-    let mut dial = 50;
-    let mut zero_count = 0;
-
-    for cmd in commands {
-        let direction = cmd.chars().next().unwrap();
-        let amount: i32 = cmd[1..].parse().unwrap();
-
-        match direction {
-            'R' => dial = (dial + amount) % 100,
-            'L' => dial = (dial - amount % 100 + 100) % 100,
-            _ => {}
-        }
+use day1::{
+    CombinationLock, Dial, DialError, LockStats, OverflowError, Rotation, StartScan, Summary,
+    read_lock_commands, read_rotations, scan_starts, summarize_parallel, trace,
+};
+use std::io::{self, BufRead, BufReader};
+
+/// 한 번에 병렬로 요약하는 명령 수
+const BATCH: usize = 1 << 20;
 
-        if dial == 0 {
-            zero_count += 1;
+fn main() {
+    // 인자: [--trace] [--dials N] [--find-start[=횟수]] [경로 | -]
+    // 경로가 없으면 input.txt, -이면 stdin
+    let mut trace_mode = false;
+    let mut dials = None;
+    // Some(None): 횟수가 가장 큰 시작 위치, Some(Some(n)): 횟수가 n인 시작 위치들
+    let mut find_start = None;
+    let mut path = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--trace" => trace_mode = true,
+            "--dials" => match args.next().and_then(|n| n.parse::<usize>().ok()) {
+                Some(n) if n > 0 => dials = Some(n),
+                _ => {
+                    eprintln!("--dials 뒤에 1 이상의 다이얼 수가 필요함");
+                    std::process::exit(1);
+                }
+            },
+            "--find-start" => find_start = Some(None),
+            _ if arg.starts_with("--find-start=") => match arg["--find-start=".len()..].parse() {
+                Ok(count) => find_start = Some(Some(count)),
+                Err(_) => {
+                    eprintln!("--find-start= 뒤에 횟수가 필요함: {}", arg);
+                    std::process::exit(1);
+                }
+            },
+            _ if arg.starts_with("--") => {
+                eprintln!("알 수 없는 인자: {}", arg);
+                std::process::exit(1);
+            }
+            _ => path = Some(arg),
         }
     }
+    let path = path.as_deref().unwrap_or("input.txt");
+
+    let reader: Box<dyn BufRead> = if path == "-" {
+        Box::new(io::stdin().lock())
+    } else {
+        match std::fs::File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(error) => {
+                eprintln!("{}: {}", path, error);
+                std::process::exit(1);
+            }
+        }
+    };
 
-    (dial, zero_count)
-}
+    if let Some(dials) = dials {
+        run_lock(path, reader, dials, trace_mode);
+        return;
+    }
 
-fn password_part2(commands: Vec<String>) -> (i32, i32) {
-    let mut dial = 50;
-    let mut zero_count = 0;
+    let dial = Dial::default();
+    let mut bad_lines = 0;
+    // 잘못된 줄은 보고하고 건너뜀, 읽기 실패는 즉시 종료
+    let rotations =
+        read_rotations(reader).filter_map(|result| skip_bad_line(path, result, &mut bad_lines));
+
+    if let Some(count) = find_start {
+        let scan = scan_starts(dial.size(), rotations);
+        if bad_lines > 0 {
+            eprintln!("잘못된 줄 {}개", bad_lines);
+            std::process::exit(1);
+        }
+        if let Err(error) = scan.and_then(|scan| print_starts(&scan, count)) {
+            eprintln!("{}: {}", path, error);
+            std::process::exit(1);
+        }
+        return;
+    }
 
-    for cmd in commands {
-        let direction = cmd.chars().next().unwrap();
-        let amount: i32 = cmd[1..].parse().unwrap();
-        let old_dial = dial;
+    let result = if trace_mode {
+        run_traced(dial, rotations)
+    } else {
+        run_batched(dial, rotations)
+    };
 
-        match direction {
-            'R' => dial = (dial + amount) % 100,
-            'L' => dial = (dial - amount % 100 + 100) % 100,
-            _ => {}
-        }
+    if bad_lines > 0 {
+        eprintln!("잘못된 줄 {}개", bad_lines);
+        std::process::exit(1);
+    }
 
-        // 0을 거친 횟수 계산
-        let crosses = match direction {
-            'R' => (old_dial + amount) / 100,
-            'L' => {
-                if old_dial == 0 {
-                    amount / 100
-                } else {
-                    (amount - old_dial + 100) / 100
-                }
-            }
-            _ => 0,
-        };
-        zero_count += crosses;
+    match result {
+        Ok((final_position, zero1, zero2)) => {
+            println!("Part1 - Final: {}, Zero count: {}", final_position, zero1);
+            println!("Part2 - Final: {}, Zero count: {}", final_position, zero2);
+        }
+        Err(error) => {
+            eprintln!("{}: {}", path, error);
+            std::process::exit(1);
+        }
     }
+}
 
-    (dial, zero_count)
+/// --find-start: 시작 위치를 모를 때 횟수가 가장 큰 시작 위치,
+/// --find-start=n: 횟수가 n인 시작 위치들
+fn print_starts(scan: &StartScan, count: Option<u64>) -> Result<(), OverflowError> {
+    match count {
+        None => {
+            let (start1, zero1) = scan.best_start_part1()?;
+            let (start2, zero2) = scan.best_start_part2()?;
+            println!("Part1 - Best start: {}, Zero count: {}", start1, zero1);
+            println!("Part2 - Best start: {}, Zero count: {}", start2, zero2);
+        }
+        Some(count) => {
+            println!("Part1 - Starts: {:?}", scan.starts_matching_part1(count)?);
+            println!("Part2 - Starts: {:?}", scan.starts_matching_part2(count)?);
+        }
+    }
+    Ok(())
 }
 
-fn main() {
-    let input = std::fs::read_to_string("input.txt").unwrap();
-    let commands: Vec<String> = input.lines().map(|s| s.to_string()).collect();
+/// --trace: 명령마다 회전 전후 위치, 0에 멈춘 횟수, 0을 거친 횟수 출력
+fn run_traced(
+    dial: Dial,
+    rotations: impl Iterator<Item = Rotation>,
+) -> Result<(u64, u64, u64), OverflowError> {
+    println!(
+        "{:>6} {:>12} {:>5} {:>5} {:>5} {:>5}",
+        "#", "command", "from", "to", "land", "zero"
+    );
+    let mut events = trace(dial, rotations);
+    let (mut zero1, mut zero2) = (0u64, 0u64);
+    for (i, event) in events.by_ref().enumerate() {
+        let event = event?;
+        println!(
+            "{:>6} {:>12} {:>5} {:>5} {:>5} {:>5}",
+            i + 1,
+            event.rotation.to_string(),
+            event.from,
+            event.to,
+            event.landings,
+            event.zero_hits
+        );
+        zero1 = zero1.checked_add(event.landings).ok_or(OverflowError)?;
+        zero2 = zero2.checked_add(event.zero_hits).ok_or(OverflowError)?;
+    }
+    Ok((events.dial().position(), zero1, zero2))
+}
 
-    let (final1, zero1) = password_part1(commands.clone());
-    println!("Part1 - Final: {}, Zero count: {}", final1, zero1);
+/// BATCH개씩 모아 스레드 수만큼 나눠 병렬로 요약, 메모리는 BATCH에 비례
+fn run_batched(
+    dial: Dial,
+    rotations: impl Iterator<Item = Rotation>,
+) -> Result<(u64, u64, u64), OverflowError> {
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let mut total = Summary::identity(dial.size());
+    let mut batch = Vec::with_capacity(BATCH);
+    for rotation in rotations {
+        batch.push(rotation);
+        if batch.len() == BATCH {
+            total = total.then(&summarize_parallel(dial, &batch, threads)?)?;
+            batch.clear();
+        }
+    }
+    total = total.then(&summarize_parallel(dial, &batch, threads)?)?;
+    total.apply(dial.position())
+}
 
-    let (final2, zero2) = password_part2(commands);
-    println!("Part2 - Final: {}, Zero count: {}", final2, zero2);
+/// 잘못된 줄은 보고하고 건너뜀, 읽기 실패는 즉시 종료
+fn skip_bad_line<T>(path: &str, result: Result<T, DialError>, bad_lines: &mut usize) -> Option<T> {
+    match result {
+        Ok(command) => Some(command),
+        Err(DialError::Parse(error)) => {
+            eprintln!("{}:{}", path, error);
+            *bad_lines += 1;
+            None
+        }
+        Err(error) => {
+            eprintln!("{}: {}", path, error);
+            std::process::exit(1);
+        }
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn test_commands() -> Vec<String> {
-        vec![
-            "L68".to_string(),
-            "L30".to_string(),
-            "R48".to_string(),
-            "L5".to_string(),
-            "R60".to_string(),
-            "L55".to_string(),
-            "L1".to_string(),
-            "L99".to_string(),
-            "R14".to_string(),
-            "L82".to_string(),
-        ]
+/// --dials N: `<번호>:<명령>` 줄을 크기 100, 시작 50인 다이얼 N개에 적용
+fn run_lock(path: &str, reader: Box<dyn BufRead>, dials: usize, trace_mode: bool) {
+    let mut lock = CombinationLock::new(vec![Dial::default(); dials]);
+    let mut stats = LockStats::new(&lock);
+    let mut bad_lines = 0;
+    let commands =
+        read_lock_commands(reader).filter_map(|result| skip_bad_line(path, result, &mut bad_lines));
+
+    if trace_mode {
+        println!(
+            "{:>6} {:>12} {:>5} {:>5} {:>5} {:>5} {:>8}",
+            "#", "command", "from", "to", "land", "zero", "unlocked"
+        );
+    }
+    for (i, command) in commands.enumerate() {
+        let event = lock.apply(command).and_then(|event| {
+            stats.record(&event)?;
+            Ok(event)
+        });
+        let event = match event {
+            Ok(event) => event,
+            Err(error) => {
+                eprintln!("{}: {}", path, error);
+                std::process::exit(1);
+            }
+        };
+        if trace_mode {
+            println!(
+                "{:>6} {:>12} {:>5} {:>5} {:>5} {:>5} {:>8}",
+                i + 1,
+                command.to_string(),
+                event.event.from,
+                event.event.to,
+                event.event.landings,
+                event.event.zero_hits,
+                if event.unlocked { "*" } else { "" }
+            );
+        }
     }
 
-    #[test]
-    fn test_password_part1() {
-        assert_eq!(password_part1(test_commands()), (32, 3));
+    if bad_lines > 0 {
+        eprintln!("잘못된 줄 {}개", bad_lines);
+        std::process::exit(1);
     }
 
-    #[test]
-    fn test_password_part2() {
-        assert_eq!(password_part2(test_commands()), (32, 6));
+    for i in 0..dials {
+        println!(
+            "Dial {} - Final: {}, Part1 zero count: {}, Part2 zero count: {}",
+            i, stats.positions[i], stats.landings[i], stats.zero_hits[i]
+        );
+    }
+    match (stats.total_landings(), stats.total_zero_hits()) {
+        (Ok(zero1), Ok(zero2)) => {
+            println!(
+                "Total - Part1 zero count: {}, Part2 zero count: {}",
+                zero1, zero2
+            )
+        }
+        _ => {
+            eprintln!("{}: {}", path, OverflowError);
+            std::process::exit(1);
+        }
     }
+    println!("Unlocked: {}", stats.unlocks);
 }
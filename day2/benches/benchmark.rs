@@ -1,100 +1,124 @@
-use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
 use day2::{
-    sum_invalid_part1_bruteforce, sum_invalid_part1_optimized, sum_invalid_part2_bruteforce,
-    sum_invalid_part2_optimized,
+    checked_sum_invalid_part1, checked_sum_invalid_part2, merge_ranges, parse_ranges,
+    sum_invalid_part1_bruteforce, sum_invalid_part2_bruteforce, sum_invalid_part2_optimized,
 };
 
-/// 다양한 범위 크기로 Part 1 벤치마크
-fn bench_part1(c: &mut Criterion) {
-    let mut group = c.benchmark_group("Part1");
-
-    // 작은 범위 (12개)
-    let small_range = (11u64, 22u64);
-    group.bench_with_input(
-        BenchmarkId::new("bruteforce", "small(12)"),
-        &small_range,
-        |b, &(start, end)| b.iter(|| sum_invalid_part1_bruteforce(black_box(start), black_box(end))),
-    );
-    group.bench_with_input(
-        BenchmarkId::new("optimized", "small(12)"),
-        &small_range,
-        |b, &(start, end)| b.iter(|| sum_invalid_part1_optimized(black_box(start), black_box(end))),
-    );
-
-    // 중간 범위 (1,000개)
-    let medium_range = (1u64, 1000u64);
-    group.bench_with_input(
-        BenchmarkId::new("bruteforce", "medium(1K)"),
-        &medium_range,
-        |b, &(start, end)| b.iter(|| sum_invalid_part1_bruteforce(black_box(start), black_box(end))),
-    );
-    group.bench_with_input(
-        BenchmarkId::new("optimized", "medium(1K)"),
-        &medium_range,
-        |b, &(start, end)| b.iter(|| sum_invalid_part1_optimized(black_box(start), black_box(end))),
-    );
-
-    // 큰 범위 (100,000개)
-    let large_range = (1u64, 100_000u64);
-    group.bench_with_input(
-        BenchmarkId::new("bruteforce", "large(100K)"),
-        &large_range,
-        |b, &(start, end)| b.iter(|| sum_invalid_part1_bruteforce(black_box(start), black_box(end))),
-    );
-    group.bench_with_input(
-        BenchmarkId::new("optimized", "large(100K)"),
-        &large_range,
-        |b, &(start, end)| b.iter(|| sum_invalid_part1_optimized(black_box(start), black_box(end))),
-    );
+/// 범위 [1, size]의 크기: 10 ~ 10^18
+const SIZES: [u64; 8] = [
+    10,
+    100,
+    1_000,
+    1_000_000,
+    1_000_000_000,
+    1_000_000_000_000,
+    1_000_000_000_000_000,
+    1_000_000_000_000_000_000,
+];
+
+/// 브루트포스는 범위 크기에 비례하므로 이보다 크면 건너뜀
+const BRUTEFORCE_LIMIT: u64 = 1_000_000;
+
+/// HashSet 버전은 무효 ID 개수 (대략 sqrt(범위))에 비례하므로 이보다 크면 건너뜀
+const HASHSET_LIMIT: u64 = 1_000_000_000_000;
+
+/// 벤치마크할 구현 하나
+struct Case {
+    name: &'static str,
+    /// 이 범위 크기까지만 실행
+    max_size: u64,
+    /// [start, end]의 합계, 최적화 버전은 u64를 넘을 수 있어 u128로 계산
+    run: fn(u64, u64) -> Option<u128>,
+}
+
+const PART1: [Case; 2] = [
+    Case {
+        name: "bruteforce",
+        max_size: BRUTEFORCE_LIMIT,
+        run: |start, end| Some(sum_invalid_part1_bruteforce(start, end) as u128),
+    },
+    Case {
+        name: "optimized",
+        max_size: u64::MAX,
+        run: |start, end| checked_sum_invalid_part1(start as u128, end as u128, 10),
+    },
+];
+
+const PART2: [Case; 3] = [
+    Case {
+        name: "bruteforce",
+        max_size: BRUTEFORCE_LIMIT,
+        run: |start, end| Some(sum_invalid_part2_bruteforce(start, end) as u128),
+    },
+    Case {
+        name: "optimized",
+        max_size: HASHSET_LIMIT,
+        run: |start, end| Some(sum_invalid_part2_optimized(start, end) as u128),
+    },
+    Case {
+        name: "closed_form",
+        max_size: u64::MAX,
+        run: |start, end| checked_sum_invalid_part2(start as u128, end as u128, 10),
+    },
+];
+
+/// [1, size] 범위를 크기별로, 처리량은 초당 훑은 ID 수
+fn bench_sizes(c: &mut Criterion, name: &str, cases: &[Case]) {
+    let mut group = c.benchmark_group(name);
+    group.sample_size(20);
+
+    for size in SIZES {
+        group.throughput(Throughput::Elements(size));
+        let label = format!("10^{}", size.ilog10());
+        for case in cases.iter().filter(|case| size <= case.max_size) {
+            group.bench_with_input(BenchmarkId::new(case.name, &label), &size, |b, &size| {
+                b.iter(|| (case.run)(black_box(1), black_box(size)))
+            });
+        }
+    }
 
     group.finish();
 }
 
-/// 다양한 범위 크기로 Part 2 벤치마크
+fn bench_part1(c: &mut Criterion) {
+    bench_sizes(c, "Part1", &PART1);
+}
+
 fn bench_part2(c: &mut Criterion) {
-    let mut group = c.benchmark_group("Part2");
-
-    // 작은 범위 (12개)
-    let small_range = (11u64, 22u64);
-    group.bench_with_input(
-        BenchmarkId::new("bruteforce", "small(12)"),
-        &small_range,
-        |b, &(start, end)| b.iter(|| sum_invalid_part2_bruteforce(black_box(start), black_box(end))),
-    );
-    group.bench_with_input(
-        BenchmarkId::new("optimized", "small(12)"),
-        &small_range,
-        |b, &(start, end)| b.iter(|| sum_invalid_part2_optimized(black_box(start), black_box(end))),
-    );
-
-    // 중간 범위 (1,000개)
-    let medium_range = (1u64, 1000u64);
-    group.bench_with_input(
-        BenchmarkId::new("bruteforce", "medium(1K)"),
-        &medium_range,
-        |b, &(start, end)| b.iter(|| sum_invalid_part2_bruteforce(black_box(start), black_box(end))),
-    );
-    group.bench_with_input(
-        BenchmarkId::new("optimized", "medium(1K)"),
-        &medium_range,
-        |b, &(start, end)| b.iter(|| sum_invalid_part2_optimized(black_box(start), black_box(end))),
-    );
-
-    // 큰 범위 (100,000개)
-    let large_range = (1u64, 100_000u64);
-    group.bench_with_input(
-        BenchmarkId::new("bruteforce", "large(100K)"),
-        &large_range,
-        |b, &(start, end)| b.iter(|| sum_invalid_part2_bruteforce(black_box(start), black_box(end))),
-    );
-    group.bench_with_input(
-        BenchmarkId::new("optimized", "large(100K)"),
-        &large_range,
-        |b, &(start, end)| b.iter(|| sum_invalid_part2_optimized(black_box(start), black_box(end))),
-    );
+    bench_sizes(c, "Part2", &PART2);
+}
+
+/// 실제 입력 (input.txt) 전체를 두 파트 모두 실행
+fn bench_input(c: &mut Criterion) {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+    let input = std::fs::read_to_string(path).expect("input.txt 읽기 실패");
+    let ranges = merge_ranges(&parse_ranges::<u64>(&input).expect("input.txt 파싱 실패"));
+    // 모든 범위의 크기 합
+    let total: u64 = ranges.iter().map(|&(start, end)| end - start + 1).sum();
+    let largest = ranges.iter().map(|&(start, end)| end - start + 1).max();
+
+    let mut group = c.benchmark_group("Input");
+    group.sample_size(20);
+    group.throughput(Throughput::Elements(total));
+
+    for (part, cases) in [("part1", &PART1[..]), ("part2", &PART2[..])] {
+        for case in cases
+            .iter()
+            .filter(|case| largest.is_some_and(|size| size <= case.max_size))
+        {
+            group.bench_function(BenchmarkId::new(case.name, part), |b| {
+                b.iter(|| {
+                    black_box(&ranges)
+                        .iter()
+                        .map(|&(start, end)| (case.run)(start, end))
+                        .sum::<Option<u128>>()
+                })
+            });
+        }
+    }
 
     group.finish();
 }
 
-criterion_group!(benches, bench_part1, bench_part2);
+criterion_group!(benches, bench_part1, bench_part2, bench_input);
 criterion_main!(benches);
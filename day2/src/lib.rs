@@ -1,9 +1,112 @@
-use std::collections::HashSet;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+use std::fmt;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+// ========== ID 정수 타입 ==========
+
+/// ID로 쓰는 부호 없는 정수: u64 (10진법 20자리), u128 (10진법 39자리)
+///
+/// 합계는 같은 타입에 checked 연산으로 누적, 넘치면 None
+pub trait Id:
+    Copy
+    + Ord
+    + fmt::Debug
+    + fmt::Display
+    + From<u32>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const MAX: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_pow(self, exp: u32) -> Option<Self>;
+    fn overflowing_add(self, rhs: Self) -> (Self, bool);
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    fn div_ceil(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_id {
+    ($($t:ty),*) => {$(
+        impl Id for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const MAX: Self = <$t>::MAX;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+            fn checked_pow(self, exp: u32) -> Option<Self> {
+                <$t>::checked_pow(self, exp)
+            }
+            fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+                <$t>::overflowing_add(self, rhs)
+            }
+            fn wrapping_sub(self, rhs: Self) -> Self {
+                <$t>::wrapping_sub(self, rhs)
+            }
+            fn div_ceil(self, rhs: Self) -> Self {
+                <$t>::div_ceil(self, rhs)
+            }
+        }
+    )*};
+}
+
+impl_id!(u64, u128);
+
+/// u64 합계 함수가 넘쳤을 때의 메시지
+const SUM_OVERFLOW: &str = "합계가 u64 범위를 넘음 (checked_* 함수 사용)";
+
+// ========== 진법 ==========
+
+/// 지원하는 진법 범위 (char::to_digit과 같음)
+pub const RADIX_RANGE: std::ops::RangeInclusive<u32> = 2..=36;
+
+fn check_radix(radix: u32) {
+    assert!(
+        RADIX_RANGE.contains(&radix),
+        "진법은 2..=36이어야 함: {}",
+        radix
+    );
+}
+
+/// n을 radix 진법으로 쓴 자릿수 (높은 자리부터, 0은 [0])
+fn digits<T: Id>(mut n: T, radix: u32) -> Vec<T> {
+    let radix = T::from(radix);
+    let mut digits = Vec::new();
+    loop {
+        digits.push(n % radix);
+        n = n / radix;
+        if n == T::ZERO {
+            break;
+        }
+    }
+    digits.reverse();
+    digits
+}
+
+// ========== 무효 ID 판정 ==========
 
 /// Part 1: 숫자가 "무효 ID"인지 확인 (패턴이 정확히 2번 반복)
 /// 예: 11, 6464, 123123
 pub fn is_invalid_part1(n: u64) -> bool {
-    let s = n.to_string();
+    is_invalid_part1_radix(n, 10)
+}
+
+/// Part 1을 radix 진법으로 확인
+/// 예: 2진법 1010 (= 10), 16진법 ABAB (= 43947)
+pub fn is_invalid_part1_radix<T: Id>(n: T, radix: u32) -> bool {
+    check_radix(radix);
+    let s = digits(n, radix);
     let len = s.len();
 
     // 짝수 길이여야 2번 반복 패턴 가능
@@ -18,7 +121,14 @@ pub fn is_invalid_part1(n: u64) -> bool {
 /// Part 2: 숫자가 "무효 ID"인지 확인 (패턴이 2번 이상 반복)
 /// 예: 12341234 (2회), 123123123 (3회), 1212121212 (5회), 1111111 (7회)
 pub fn is_invalid_part2(n: u64) -> bool {
-    let s = n.to_string();
+    is_invalid_part2_radix(n, 10)
+}
+
+/// Part 2를 radix 진법으로 확인
+/// 예: 2진법 101010 (= 42), 16진법 FFF (= 4095)
+pub fn is_invalid_part2_radix<T: Id>(n: T, radix: u32) -> bool {
+    check_radix(radix);
+    let s = digits(n, radix);
     let len = s.len();
 
     // 가능한 모든 패턴 길이 시도 (1부터 len/2까지)
@@ -38,12 +148,48 @@ pub fn is_invalid_part2(n: u64) -> bool {
 
 /// Part 1 브루트포스: 범위 내 모든 숫자 순회
 pub fn sum_invalid_part1_bruteforce(start: u64, end: u64) -> u64 {
-    (start..=end).filter(|&n| is_invalid_part1(n)).sum()
+    sum_invalid_part1_bruteforce_radix(start, end, 10)
+}
+
+/// Part 1 브루트포스 (radix 진법)
+pub fn sum_invalid_part1_bruteforce_radix(start: u64, end: u64, radix: u32) -> u64 {
+    checked_sum_invalid_part1_bruteforce(start, end, radix).expect(SUM_OVERFLOW)
+}
+
+/// Part 1 브루트포스 (u64 또는 u128), 합계가 T를 넘으면 None
+pub fn checked_sum_invalid_part1_bruteforce<T: Id>(start: T, end: T, radix: u32) -> Option<T> {
+    checked_sum_bruteforce(start, end, |n| is_invalid_part1_radix(n, radix))
 }
 
 /// Part 2 브루트포스: 범위 내 모든 숫자 순회
 pub fn sum_invalid_part2_bruteforce(start: u64, end: u64) -> u64 {
-    (start..=end).filter(|&n| is_invalid_part2(n)).sum()
+    sum_invalid_part2_bruteforce_radix(start, end, 10)
+}
+
+/// Part 2 브루트포스 (radix 진법)
+pub fn sum_invalid_part2_bruteforce_radix(start: u64, end: u64, radix: u32) -> u64 {
+    checked_sum_invalid_part2_bruteforce(start, end, radix).expect(SUM_OVERFLOW)
+}
+
+/// Part 2 브루트포스 (u64 또는 u128), 합계가 T를 넘으면 None
+pub fn checked_sum_invalid_part2_bruteforce<T: Id>(start: T, end: T, radix: u32) -> Option<T> {
+    checked_sum_bruteforce(start, end, |n| is_invalid_part2_radix(n, radix))
+}
+
+/// [start, end]를 하나씩 돌며 is_invalid인 수의 합, end가 T::MAX여도 넘치지 않음
+fn checked_sum_bruteforce<T: Id>(start: T, end: T, is_invalid: impl Fn(T) -> bool) -> Option<T> {
+    let mut sum = T::ZERO;
+    let mut n = start;
+    while n <= end {
+        if is_invalid(n) {
+            sum = sum.checked_add(n)?;
+        }
+        match n.checked_add(T::ONE) {
+            Some(next) => n = next,
+            None => break,
+        }
+    }
+    Some(sum)
 }
 
 // ========== 최적화 버전 ==========
@@ -51,52 +197,130 @@ pub fn sum_invalid_part2_bruteforce(start: u64, end: u64) -> u64 {
 /// Part 1 최적화: 등차수열 공식으로 범위 내 무효 ID 합계 계산
 /// O(range_size) 대신 O(1)
 pub fn sum_invalid_part1_optimized(start: u64, end: u64) -> u64 {
-    let mut sum = 0u64;
+    sum_invalid_part1_optimized_radix(start, end, 10)
+}
 
-    // 가능한 모든 패턴 길이 k에 대해
-    for k in 1..=10u32 {
-        let multiplier = 10u64.pow(k) + 1; // 예: k=2 -> 101, k=3 -> 1001
+/// Part 1 최적화 (radix 진법)
+/// 합계가 u64를 넘으면 panic, 넘칠 수 있으면 checked_sum_invalid_part1 사용
+pub fn sum_invalid_part1_optimized_radix(start: u64, end: u64, radix: u32) -> u64 {
+    checked_sum_invalid_part1(start, end, radix).expect(SUM_OVERFLOW)
+}
+
+/// Part 1 최적화 (u64 또는 u128), 합계가 T를 넘으면 None
+/// k자리 패턴 x가 2번 반복된 수는 x * (radix^k + 1)
+pub fn checked_sum_invalid_part1<T: Id>(start: T, end: T, radix: u32) -> Option<T> {
+    repeats_total(radix, 2, |block| block.sum_in(start, end))
+}
 
-        // 패턴은 정확히 k자리여야 함 (앞에 0 불가)
-        let pattern_min_natural = if k == 1 { 1 } else { 10u64.pow(k - 1) };
-        let pattern_max_natural = 10u64.pow(k) - 1;
+/// 모든 패턴 길이 k의 repeats번 반복 블록에 measure를 적용해 더함
+/// 길이가 다른 블록끼리는 겹치지 않음
+fn repeats_total<T: Id>(
+    radix: u32,
+    repeats: u32,
+    measure: impl Fn(&Block<T>) -> Option<T>,
+) -> Option<T> {
+    check_radix(radix);
+    let radix = T::from(radix);
+    let mut total = T::ZERO;
 
-        // 범위 제약에서 패턴 경계 계산
-        let pattern_min_from_range = start.div_ceil(multiplier);
-        let pattern_max_from_range = end / multiplier;
+    // 가능한 모든 패턴 길이 k에 대해, 승수가 T를 넘으면 끝
+    // 예: 10진법 2번 반복 k=2 -> 101, k=3 -> 1001
+    for k in 1u32.. {
+        let Some(block) = Block::new(radix, k, repeats) else {
+            break;
+        };
+        total = total.checked_add(measure(&block)?)?;
+    }
 
-        let p_min = pattern_min_natural.max(pattern_min_from_range);
-        let p_max = pattern_max_natural.min(pattern_max_from_range);
+    Some(total)
+}
+
+/// p자리 패턴을 r번 반복한 수들: pattern * multiplier
+/// pattern은 정확히 p자리 (앞에 0 불가), multiplier = radix^(p*(r-1)) + ... + radix^p + 1
+#[derive(Debug, Clone, Copy)]
+struct Block<T> {
+    pattern_min: T,
+    pattern_max: T,
+    multiplier: T,
+}
 
-        if p_min <= p_max {
-            let count = p_max - p_min + 1;
-            let pattern_sum = (p_min + p_max) * count / 2; // 등차수열 합
-            sum += pattern_sum * multiplier;
+impl<T: Id> Block<T> {
+    /// 승수가 T를 넘으면 그런 수가 T에 없으므로 None
+    fn new(radix: T, p: u32, r: u32) -> Option<Self> {
+        let block = radix.checked_pow(p)?;
+        let mut multiplier = T::ONE;
+        for _ in 1..r {
+            multiplier = multiplier.checked_mul(block)?.checked_add(T::ONE)?;
         }
+        Some(Block {
+            pattern_min: radix.checked_pow(p - 1)?,
+            pattern_max: block - T::ONE,
+            multiplier,
+        })
     }
 
-    sum
+    /// [start, end] 범위에서 유효한 패턴 범위, 없으면 None
+    fn patterns_in(&self, start: T, end: T) -> Option<(T, T)> {
+        let p_min = self.pattern_min.max(start.div_ceil(self.multiplier));
+        let p_max = self.pattern_max.min(end / self.multiplier);
+        (p_min <= p_max).then_some((p_min, p_max))
+    }
+
+    /// [start, end]에 있는 수의 개수
+    fn count_in(&self, start: T, end: T) -> T {
+        self.patterns_in(start, end)
+            .map_or(T::ZERO, |(p_min, p_max)| p_max - p_min + T::ONE)
+    }
+
+    /// [start, end]에 있는 수의 합 (등차수열), 합계가 T를 넘으면 None
+    fn sum_in(&self, start: T, end: T) -> Option<T> {
+        let Some((p_min, p_max)) = self.patterns_in(start, end) else {
+            return Some(T::ZERO);
+        };
+
+        // (p_min + p_max) * count / 2, 둘 중 짝수인 쪽을 먼저 나눔
+        let two = T::from(2);
+        let count = p_max - p_min + T::ONE;
+        let ends = p_min.checked_add(p_max)?;
+        let pattern_sum = if count % two == T::ZERO {
+            (count / two).checked_mul(ends)?
+        } else {
+            count.checked_mul(ends / two)?
+        };
+        pattern_sum.checked_mul(self.multiplier)
+    }
 }
 
 /// Part 2 최적화: 범위 내 모든 무효 ID 찾기 (패턴 2회 이상 반복)
 /// 범위 내 모든 숫자 대신 패턴만 열거
 pub fn sum_invalid_part2_optimized(start: u64, end: u64) -> u64 {
+    sum_invalid_part2_optimized_radix(start, end, 10)
+}
+
+/// Part 2 최적화 (radix 진법)
+pub fn sum_invalid_part2_optimized_radix(start: u64, end: u64, radix: u32) -> u64 {
+    check_radix(radix);
+    let radix = radix as u64;
     let mut invalid_ids = HashSet::new();
 
-    // 각 패턴 길이 p에 대해
-    for p in 1..=10u32 {
+    // 각 패턴 길이 p에 대해, radix^p가 u64를 넘으면 2번 반복도 불가
+    for p in 1u32.. {
+        let Some(block) = radix.checked_pow(p) else {
+            break;
+        };
         // 패턴은 정확히 p자리여야 함 (앞에 0 불가)
-        let pattern_min = if p == 1 { 1 } else { 10u64.pow(p - 1) };
-        let pattern_max = 10u64.pow(p) - 1;
+        let pattern_min = if p == 1 { 1 } else { radix.pow(p - 1) };
+        let pattern_max = block - 1;
 
         // 각 반복 횟수 r에 대해 (최소 2회)
-        for r in 2..=20u32 {
-            if p * r > 20 {
-                break; // u64 최대 약 20자리
-            }
-
-            // 승수 계산: 10^(p*(r-1)) + 10^(p*(r-2)) + ... + 1
-            let multiplier: u64 = (0..r).map(|i| 10u64.pow(p * i)).sum();
+        // 승수 계산: radix^(p*(r-1)) + radix^(p*(r-2)) + ... + 1
+        let mut multiplier = 1u64;
+        for _ in 2u32.. {
+            // u64를 넘으면 더 많은 반복도 불가
+            let Some(next) = multiplier.checked_mul(block).and_then(|m| m.checked_add(1)) else {
+                break;
+            };
+            multiplier = next;
 
             // [start, end] 범위에서 유효한 패턴 범위 찾기
             let p_min_from_range = start.div_ceil(multiplier);
@@ -114,7 +338,816 @@ pub fn sum_invalid_part2_optimized(start: u64, end: u64) -> u64 {
         }
     }
 
-    invalid_ids.iter().sum()
+    invalid_ids
+        .iter()
+        .try_fold(0u64, |sum, &id| sum.checked_add(id))
+        .expect(SUM_OVERFLOW)
+}
+
+// ========== 닫힌 형식 버전 ==========
+
+/// 뫼비우스 함수 μ(n): 제곱 인수가 있으면 0, 아니면 (-1)^(소인수 개수)
+fn mobius(mut n: u32) -> i32 {
+    let mut result = 1;
+    let mut f = 2;
+    while f * f <= n {
+        if n.is_multiple_of(f) {
+            n /= f;
+            if n.is_multiple_of(f) {
+                return 0;
+            }
+            result = -result;
+        }
+        f += 1;
+    }
+    if n > 1 {
+        result = -result;
+    }
+    result
+}
+
+/// 넘친 횟수를 함께 세는 합: 값 = carry * 2^bits + low
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Wide<T> {
+    carry: u32,
+    low: T,
+}
+
+impl<T: Id> Wide<T> {
+    fn add(self, value: T) -> Self {
+        let (low, overflow) = self.low.overflowing_add(value);
+        Wide {
+            carry: self.carry + overflow as u32,
+            low,
+        }
+    }
+
+    /// self - other가 T에 들어가면 Some (self >= other 가정)
+    fn checked_sub(self, other: Self) -> Option<T> {
+        let borrow = (self.low < other.low) as u32;
+        (self.carry == other.carry + borrow).then(|| self.low.wrapping_sub(other.low))
+    }
+}
+
+/// Part 2 닫힌 형식: 무효 ID를 하나도 열거하지 않고 합계 계산
+/// 비용은 자릿수에만 의존 (범위 크기, 무효 ID 개수와 무관)
+pub fn sum_invalid_part2_closed_form(start: u64, end: u64) -> u64 {
+    sum_invalid_part2_closed_form_radix(start, end, 10)
+}
+
+/// Part 2 닫힌 형식 (radix 진법)
+/// 합계가 u64를 넘으면 panic, 넘칠 수 있으면 checked_sum_invalid_part2 사용
+pub fn sum_invalid_part2_closed_form_radix(start: u64, end: u64, radix: u32) -> u64 {
+    checked_sum_invalid_part2(start, end, radix).expect(SUM_OVERFLOW)
+}
+
+/// Part 2 닫힌 형식 (u64 또는 u128), 합계가 T를 넘으면 None
+///
+/// L자리 수에서 S(d) = "주기가 d의 약수인 수"의 합 (d자리 패턴 반복, 등차수열),
+/// f(e) = "최소 주기가 정확히 e인 수"의 합이라 하면 S(d) = Σ_{e|d} f(e)이고
+/// 뫼비우스 반전으로 f(e) = Σ_{d|e} μ(e/d) S(d).
+/// 무효 ID 합 = Σ_{e|L, e<L} f(e) = -Σ_{d|L, d<L} μ(L/d) S(d)
+/// 이라 같은 ID를 여러 번 세지 않음 (HashSet 불필요)
+///
+/// S(d)는 모두 무효 ID의 합이라 답보다 크지 않으므로 각각 checked로 계산하고,
+/// 부호가 섞인 합만 넘친 횟수를 세며 누적
+pub fn checked_sum_invalid_part2<T: Id>(start: T, end: T, radix: u32) -> Option<T> {
+    part2_total(radix, |block| block.sum_in(start, end))
+}
+
+/// 자릿수 L마다 -Σ_{d|L, d<L} μ(L/d) measure(d자리 패턴 L/d번 반복)를 더함
+/// measure는 블록의 합이나 개수처럼 겹치는 부분에 더해지는 양
+fn part2_total<T: Id>(radix: u32, measure: impl Fn(&Block<T>) -> Option<T>) -> Option<T> {
+    check_radix(radix);
+    let radix = T::from(radix);
+    let mut total = T::ZERO;
+
+    // 각 자릿수 L에 대해, radix^(L-1)이 T를 넘으면 L자리 수 없음
+    for len in 2u32.. {
+        if radix.checked_pow(len - 1).is_none() {
+            break;
+        }
+        let zero = Wide {
+            carry: 0,
+            low: T::ZERO,
+        };
+        let (mut plus, mut minus) = (zero, zero);
+        for d in (1..len).filter(|d| len.is_multiple_of(*d)) {
+            // 승수가 T를 넘으면 그런 수가 없으므로 0
+            let Some(block) = Block::new(radix, d, len / d) else {
+                continue;
+            };
+            match -mobius(len / d) {
+                1 => plus = plus.add(measure(&block)?),
+                -1 => minus = minus.add(measure(&block)?),
+                _ => {}
+            }
+        }
+        total = total.checked_add(plus.checked_sub(minus)?)?;
+    }
+
+    Some(total)
+}
+
+// ========== 무효 ID 나열 ==========
+
+/// 범위 안의 무효 ID를 오름차순으로 내는 이터레이터
+///
+/// (패턴 길이, 반복 횟수) 블록마다 pattern * multiplier가 증가하는 스트림이고,
+/// 스트림의 다음 값들을 힙으로 병합. 같은 ID가 여러 블록에 있으면 (1111 = 1×4 = 11×2)
+/// 한 번만 냄. 메모리는 블록 수에 비례, 범위 크기와 무관
+pub struct InvalidIds<T> {
+    /// 블록별 (다음 패턴, 마지막 패턴, 승수)
+    streams: Vec<(T, T, T)>,
+    /// (다음 ID, 스트림 번호)의 최소 힙
+    heap: BinaryHeap<Reverse<(T, usize)>>,
+    last: Option<T>,
+}
+
+impl<T: Id> InvalidIds<T> {
+    fn new(start: T, end: T, blocks: impl Iterator<Item = Block<T>>) -> Self {
+        let mut streams = Vec::new();
+        let mut heap = BinaryHeap::new();
+        for block in blocks {
+            if let Some((p_min, p_max)) = block.patterns_in(start, end) {
+                heap.push(Reverse((p_min * block.multiplier, streams.len())));
+                streams.push((p_min, p_max, block.multiplier));
+            }
+        }
+        InvalidIds {
+            streams,
+            heap,
+            last: None,
+        }
+    }
+}
+
+impl<T: Id> Iterator for InvalidIds<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            let Reverse((id, i)) = self.heap.pop()?;
+            let (pattern, last_pattern, multiplier) = &mut self.streams[i];
+            if *pattern < *last_pattern {
+                *pattern = *pattern + T::ONE;
+                self.heap.push(Reverse((*pattern * *multiplier, i)));
+            }
+            if self.last != Some(id) {
+                self.last = Some(id);
+                return Some(id);
+            }
+        }
+    }
+}
+
+/// Part 1 블록: 모든 패턴 길이 k의 2번 반복
+fn part1_blocks<T: Id>(radix: u32) -> impl Iterator<Item = Block<T>> {
+    check_radix(radix);
+    let radix = T::from(radix);
+    (1u32..).map_while(move |k| Block::new(radix, k, 2))
+}
+
+/// Part 2 블록: 모든 패턴 길이 p와 반복 횟수 r >= 2
+fn part2_blocks<T: Id>(radix: u32) -> impl Iterator<Item = Block<T>> {
+    check_radix(radix);
+    let radix = T::from(radix);
+    // radix^p가 T를 넘으면 2번 반복도 불가
+    (1u32..)
+        .map_while(move |p| radix.checked_pow(p).map(|_| p))
+        .flat_map(move |p| (2u32..).map_while(move |r| Block::new(radix, p, r)))
+}
+
+/// Part 1 무효 ID를 오름차순으로 나열 (필요할 때마다 계산)
+pub fn invalid_ids_part1(start: u64, end: u64) -> InvalidIds<u64> {
+    invalid_ids_part1_radix(start, end, 10)
+}
+
+/// Part 1 무효 ID 나열 (u64 또는 u128, radix 진법)
+pub fn invalid_ids_part1_radix<T: Id>(start: T, end: T, radix: u32) -> InvalidIds<T> {
+    InvalidIds::new(start, end, part1_blocks(radix))
+}
+
+/// Part 2 무효 ID를 오름차순으로 나열 (필요할 때마다 계산)
+pub fn invalid_ids_part2(start: u64, end: u64) -> InvalidIds<u64> {
+    invalid_ids_part2_radix(start, end, 10)
+}
+
+/// Part 2 무효 ID 나열 (u64 또는 u128, radix 진법)
+pub fn invalid_ids_part2_radix<T: Id>(start: T, end: T, radix: u32) -> InvalidIds<T> {
+    InvalidIds::new(start, end, part2_blocks(radix))
+}
+
+/// 개수는 범위 크기를 넘지 않으므로 T에 들어감
+const COUNT_FITS: &str = "무효 ID 개수는 범위 크기를 넘지 않음";
+
+/// Part 1 무효 ID 개수, 나열하지 않고 블록별 패턴 개수로 계산
+pub fn count_invalid_part1(start: u64, end: u64) -> u64 {
+    count_invalid_part1_radix(start, end, 10)
+}
+
+/// Part 1 무효 ID 개수 (u64 또는 u128, radix 진법)
+pub fn count_invalid_part1_radix<T: Id>(start: T, end: T, radix: u32) -> T {
+    repeats_total(radix, 2, |block| Some(block.count_in(start, end))).expect(COUNT_FITS)
+}
+
+/// Part 2 무효 ID 개수, 합계와 같은 뫼비우스 반전으로 중복 없이 계산
+pub fn count_invalid_part2(start: u64, end: u64) -> u64 {
+    count_invalid_part2_radix(start, end, 10)
+}
+
+/// Part 2 무효 ID 개수 (u64 또는 u128, radix 진법)
+pub fn count_invalid_part2_radix<T: Id>(start: T, end: T, radix: u32) -> T {
+    part2_total(radix, |block| Some(block.count_in(start, end))).expect(COUNT_FITS)
+}
+
+/// Part 1에서 n번째 (0부터) 무효 ID, 없으면 None
+pub fn nth_invalid_part1(start: u64, end: u64, n: u64) -> Option<u64> {
+    nth_invalid_part1_radix(start, end, n, 10)
+}
+
+/// Part 1 n번째 무효 ID (u64 또는 u128, radix 진법)
+pub fn nth_invalid_part1_radix<T: Id>(start: T, end: T, n: T, radix: u32) -> Option<T> {
+    nth_invalid(start, end, n, |to| {
+        count_invalid_part1_radix(start, to, radix)
+    })
+}
+
+/// Part 2에서 n번째 (0부터) 무효 ID, 없으면 None
+pub fn nth_invalid_part2(start: u64, end: u64, n: u64) -> Option<u64> {
+    nth_invalid_part2_radix(start, end, n, 10)
+}
+
+/// Part 2 n번째 무효 ID (u64 또는 u128, radix 진법)
+pub fn nth_invalid_part2_radix<T: Id>(start: T, end: T, n: T, radix: u32) -> Option<T> {
+    nth_invalid(start, end, n, |to| {
+        count_invalid_part2_radix(start, to, radix)
+    })
+}
+
+/// count(x) = [start, x]의 무효 ID 개수가 n보다 커지는 가장 작은 x를 이분 탐색
+/// 나열하지 않으므로 O(비트 수 × count 비용)
+fn nth_invalid<T: Id>(start: T, end: T, n: T, count: impl Fn(T) -> T) -> Option<T> {
+    if start > end || count(end) <= n {
+        return None;
+    }
+    let (mut lo, mut hi) = (start, end);
+    while lo < hi {
+        let mid = lo + (hi - lo) / T::from(2);
+        if count(mid) > n {
+            hi = mid;
+        } else {
+            lo = mid + T::ONE;
+        }
+    }
+    Some(lo)
+}
+
+// ========== 여러 범위 ==========
+
+/// 범위 목록을 시작 순으로 정렬하고 겹치거나 이어진 범위를 합침
+/// start > end인 빈 범위는 버림
+pub fn merge_ranges<T: Id>(ranges: &[(T, T)]) -> Vec<(T, T)> {
+    let mut sorted: Vec<(T, T)> = ranges
+        .iter()
+        .copied()
+        .filter(|&(start, end)| start <= end)
+        .collect();
+    sorted.sort_unstable();
+
+    let mut merged: Vec<(T, T)> = Vec::with_capacity(sorted.len());
+    for (start, end) in sorted {
+        match merged.last_mut() {
+            // 이전 범위 끝 + 1 이하에서 시작하면 이어짐 (끝이 T::MAX면 항상)
+            Some(last) if last.1.checked_add(T::ONE).is_none_or(|next| start <= next) => {
+                last.1 = last.1.max(end);
+            }
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// 여러 범위의 무효 ID 합계 (Part 1, Part 2), 두 범위에 모두 들어가는 ID도 한 번만 셈
+pub fn sum_invalid_over_ranges(ranges: &[(u64, u64)]) -> (u64, u64) {
+    let part1 = checked_sum_invalid_over_ranges(ranges, |start, end| {
+        checked_sum_invalid_part1(start, end, 10)
+    });
+    let part2 = checked_sum_invalid_over_ranges(ranges, |start, end| {
+        checked_sum_invalid_part2(start, end, 10)
+    });
+    (part1.expect(SUM_OVERFLOW), part2.expect(SUM_OVERFLOW))
+}
+
+/// 범위를 합친 뒤 범위마다 sum을 더함 (u64 또는 u128), 합계가 T를 넘으면 None
+///
+/// 합계 함수를 받으므로 진법이 다른 합계나 `IdRule::checked_sum`에도 씀
+pub fn checked_sum_invalid_over_ranges<T: Id>(
+    ranges: &[(T, T)],
+    sum: impl Fn(T, T) -> Option<T>,
+) -> Option<T> {
+    checked_sum_per_range(&merge_ranges(ranges), sum)
+}
+
+/// 합치지 않고 범위마다 sum을 더함, 겹친 ID는 범위마다 셈, T를 넘으면 None
+pub fn checked_sum_per_range<T: Id>(
+    ranges: &[(T, T)],
+    sum: impl Fn(T, T) -> Option<T>,
+) -> Option<T> {
+    ranges.iter().try_fold(T::ZERO, |total, &(start, end)| {
+        total.checked_add(sum(start, end)?)
+    })
+}
+
+// ========== 검사 규칙 ==========
+
+/// ID 검사 규칙: 브루트포스용 판정과 범위 합계/개수
+///
+/// 합계와 개수의 기본 구현은 범위를 하나씩 도는 브루트포스,
+/// 닫힌 형식이 있는 규칙은 override
+pub trait IdRule<T: Id> {
+    /// 이 규칙으로 무효인 ID인지
+    fn is_invalid(&self, n: T) -> bool;
+
+    /// [start, end]의 무효 ID 합계, T를 넘으면 None
+    fn checked_sum(&self, start: T, end: T) -> Option<T> {
+        checked_sum_bruteforce(start, end, |n| self.is_invalid(n))
+    }
+
+    /// [start, end]의 무효 ID 개수
+    fn count(&self, start: T, end: T) -> T {
+        let mut count = T::ZERO;
+        let mut n = start;
+        while n <= end {
+            if self.is_invalid(n) {
+                count = count + T::ONE;
+            }
+            match n.checked_add(T::ONE) {
+                Some(next) => n = next,
+                None => break,
+            }
+        }
+        count
+    }
+}
+
+/// 패턴이 정확히 repeats번 반복된 ID (Part 1은 10진법 2번)
+/// 예: repeats = 3이면 123123123, 777 (7 × 3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RepeatedExactly {
+    radix: u32,
+    repeats: u32,
+}
+
+impl RepeatedExactly {
+    pub fn new(radix: u32, repeats: u32) -> Self {
+        check_radix(radix);
+        assert!(repeats >= 2, "반복 횟수는 2 이상이어야 함: {}", repeats);
+        RepeatedExactly { radix, repeats }
+    }
+
+    /// Part 1 규칙: 10진법 정확히 2번 반복
+    pub fn part1() -> Self {
+        Self::new(10, 2)
+    }
+}
+
+impl<T: Id> IdRule<T> for RepeatedExactly {
+    fn is_invalid(&self, n: T) -> bool {
+        let s = digits(n, self.radix);
+        let r = self.repeats as usize;
+        if !s.len().is_multiple_of(r) {
+            return false;
+        }
+        let pattern = &s[..s.len() / r];
+        pattern.repeat(r) == s
+    }
+
+    fn checked_sum(&self, start: T, end: T) -> Option<T> {
+        repeats_total(self.radix, self.repeats, |block| block.sum_in(start, end))
+    }
+
+    fn count(&self, start: T, end: T) -> T {
+        repeats_total(self.radix, self.repeats, |block| {
+            Some(block.count_in(start, end))
+        })
+        .expect(COUNT_FITS)
+    }
+}
+
+/// 패턴이 2번 이상 반복된 ID (Part 2는 10진법)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repeated {
+    radix: u32,
+}
+
+impl Repeated {
+    pub fn new(radix: u32) -> Self {
+        check_radix(radix);
+        Repeated { radix }
+    }
+
+    /// Part 2 규칙: 10진법 2번 이상 반복
+    pub fn part2() -> Self {
+        Self::new(10)
+    }
+}
+
+impl<T: Id> IdRule<T> for Repeated {
+    fn is_invalid(&self, n: T) -> bool {
+        is_invalid_part2_radix(n, self.radix)
+    }
+
+    fn checked_sum(&self, start: T, end: T) -> Option<T> {
+        checked_sum_invalid_part2(start, end, self.radix)
+    }
+
+    fn count(&self, start: T, end: T) -> T {
+        count_invalid_part2_radix(start, end, self.radix)
+    }
+}
+
+/// 두 자리 이상의 회문 ID
+/// 예: 11, 121, 1221, 90509
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palindrome {
+    radix: u32,
+}
+
+impl Palindrome {
+    pub fn new(radix: u32) -> Self {
+        check_radix(radix);
+        Palindrome { radix }
+    }
+}
+
+impl<T: Id> IdRule<T> for Palindrome {
+    fn is_invalid(&self, n: T) -> bool {
+        let s = digits(n, self.radix);
+        s.len() >= 2 && s.iter().eq(s.iter().rev())
+    }
+}
+
+/// 두 자리 이상이고 자릿수가 줄어들지 않는 ID
+/// 예: 11, 123, 1229, 5558
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonDecreasing {
+    radix: u32,
+}
+
+impl NonDecreasing {
+    pub fn new(radix: u32) -> Self {
+        check_radix(radix);
+        NonDecreasing { radix }
+    }
+}
+
+impl<T: Id> IdRule<T> for NonDecreasing {
+    fn is_invalid(&self, n: T) -> bool {
+        let s = digits(n, self.radix);
+        s.len() >= 2 && s.windows(2).all(|w| w[0] <= w[1])
+    }
+}
+
+// ========== 최소 주기별 조회 ==========
+
+/// 최소 주기가 정확히 pattern_len자리이고 그 패턴이 repeats번 반복된 블록들에
+/// measure를 적용한 값: f(e) = Σ_{d|e} μ(e/d) S(d) (e = pattern_len)
+fn exact_period_total<T: Id>(
+    radix: T,
+    pattern_len: u32,
+    repeats: u32,
+    measure: &impl Fn(&Block<T>) -> Option<T>,
+) -> Option<T> {
+    let len = pattern_len * repeats;
+    let zero = Wide {
+        carry: 0,
+        low: T::ZERO,
+    };
+    let (mut plus, mut minus) = (zero, zero);
+    for d in (1..=pattern_len).filter(|d| pattern_len.is_multiple_of(*d)) {
+        // 승수가 T를 넘으면 그런 수가 없으므로 0
+        let Some(block) = Block::new(radix, d, len / d) else {
+            continue;
+        };
+        match mobius(pattern_len / d) {
+            1 => plus = plus.add(measure(&block)?),
+            -1 => minus = minus.add(measure(&block)?),
+            _ => {}
+        }
+    }
+    plus.checked_sub(minus)
+}
+
+/// T에 들어가는 모든 (최소 주기 길이, 반복 횟수 >= 2) 쌍, 패턴 길이 → 반복 횟수 순
+fn periods<T: Id>(radix: T) -> impl Iterator<Item = (u32, u32)> {
+    (1u32..)
+        .take_while(move |&p| Block::new(radix, p, 2).is_some())
+        .flat_map(move |p| {
+            (2u32..)
+                .take_while(move |&r| Block::new(radix, p, r).is_some())
+                .map(move |r| (p, r))
+        })
+}
+
+/// 최소 주기 패턴이 정확히 pattern_len자리이고 정확히 repeats번 반복된 ID의 합
+/// 예: pattern_len = 1, repeats = 6이면 111111 (11 × 3이나 111 × 2로 보지 않음)
+/// 합계가 T를 넘으면 None
+pub fn checked_sum_exact_period<T: Id>(
+    start: T,
+    end: T,
+    radix: u32,
+    pattern_len: u32,
+    repeats: u32,
+) -> Option<T> {
+    check_radix(radix);
+    assert!(
+        pattern_len >= 1 && repeats >= 2,
+        "패턴 길이 1 이상, 반복 2 이상"
+    );
+    exact_period_total(T::from(radix), pattern_len, repeats, &|block| {
+        block.sum_in(start, end)
+    })
+}
+
+/// checked_sum_exact_period의 개수
+pub fn count_exact_period<T: Id>(
+    start: T,
+    end: T,
+    radix: u32,
+    pattern_len: u32,
+    repeats: u32,
+) -> T {
+    check_radix(radix);
+    assert!(
+        pattern_len >= 1 && repeats >= 2,
+        "패턴 길이 1 이상, 반복 2 이상"
+    );
+    exact_period_total(T::from(radix), pattern_len, repeats, &|block| {
+        Some(block.count_in(start, end))
+    })
+    .expect(COUNT_FITS)
+}
+
+/// 최소 주기 패턴이 정확히 repeats번 반복된 ID의 합 (패턴 길이는 무관)
+pub fn checked_sum_exact_repeats<T: Id>(start: T, end: T, radix: u32, repeats: u32) -> Option<T> {
+    check_radix(radix);
+    periods(T::from(radix))
+        .filter(|&(_, r)| r == repeats)
+        .try_fold(T::ZERO, |total, (p, r)| {
+            total.checked_add(checked_sum_exact_period(start, end, radix, p, r)?)
+        })
+}
+
+/// checked_sum_exact_repeats의 개수
+pub fn count_exact_repeats<T: Id>(start: T, end: T, radix: u32, repeats: u32) -> T {
+    check_radix(radix);
+    periods(T::from(radix))
+        .filter(|&(_, r)| r == repeats)
+        .fold(T::ZERO, |total, (p, r)| {
+            total + count_exact_period(start, end, radix, p, r)
+        })
+}
+
+/// 최소 주기 패턴이 정확히 pattern_len자리인 ID의 합 (반복 횟수는 2 이상 무관)
+pub fn checked_sum_exact_pattern_len<T: Id>(
+    start: T,
+    end: T,
+    radix: u32,
+    pattern_len: u32,
+) -> Option<T> {
+    check_radix(radix);
+    periods(T::from(radix))
+        .filter(|&(p, _)| p == pattern_len)
+        .try_fold(T::ZERO, |total, (p, r)| {
+            total.checked_add(checked_sum_exact_period(start, end, radix, p, r)?)
+        })
+}
+
+/// checked_sum_exact_pattern_len의 개수
+pub fn count_exact_pattern_len<T: Id>(start: T, end: T, radix: u32, pattern_len: u32) -> T {
+    check_radix(radix);
+    periods(T::from(radix))
+        .filter(|&(p, _)| p == pattern_len)
+        .fold(T::ZERO, |total, (p, r)| {
+            total + count_exact_period(start, end, radix, p, r)
+        })
+}
+
+/// 최소 주기별 집계 한 줄
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeriodRow<T> {
+    pub pattern_len: u32,
+    pub repeats: u32,
+    pub count: T,
+    /// 합계가 T를 넘으면 None
+    pub sum: Option<T>,
+}
+
+/// [start, end]의 Part 2 무효 ID를 (최소 주기 패턴 길이, 반복 횟수)별로 나눈 표
+/// 개수가 0인 줄은 뺌, 모든 줄의 합은 Part 2 합계와 같음
+pub fn period_breakdown<T: Id>(start: T, end: T, radix: u32) -> Vec<PeriodRow<T>> {
+    check_radix(radix);
+    periods(T::from(radix))
+        .map(|(p, r)| PeriodRow {
+            pattern_len: p,
+            repeats: r,
+            count: count_exact_period(start, end, radix, p, r),
+            sum: checked_sum_exact_period(start, end, radix, p, r),
+        })
+        .filter(|row| row.count > T::ZERO)
+        .collect()
+}
+
+// ========== 범위 파싱 ==========
+
+/// 범위 목록 파싱 실패 이유
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRangeErrorKind {
+    /// 숫자가 와야 할 자리에 다른 문자 (None이면 입력 끝)
+    ExpectedNumber(Option<char>),
+    /// 시작 숫자 뒤에 '-'가 없음 (None이면 입력 끝)
+    ExpectedDash(Option<char>),
+    /// 범위 뒤에 ',' 또는 공백이 없음
+    ExpectedSeparator(char),
+    /// 숫자가 ID 타입 범위를 넘음
+    NumberTooLarge,
+}
+
+impl fmt::Display for ParseRangeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseRangeErrorKind::ExpectedNumber(Some(c)) => write!(f, "숫자가 필요함: {:?}", c),
+            ParseRangeErrorKind::ExpectedNumber(None) => write!(f, "숫자가 필요함: 입력 끝"),
+            ParseRangeErrorKind::ExpectedDash(Some(c)) => write!(f, "'-'가 필요함: {:?}", c),
+            ParseRangeErrorKind::ExpectedDash(None) => write!(f, "'-'가 필요함: 입력 끝"),
+            ParseRangeErrorKind::ExpectedSeparator(c) => {
+                write!(f, "',' 또는 공백이 필요함: {:?}", c)
+            }
+            ParseRangeErrorKind::NumberTooLarge => write!(f, "숫자가 너무 큼"),
+        }
+    }
+}
+
+/// 범위 목록 파싱 에러, offset은 입력의 바이트 위치 (0부터)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRangeError {
+    pub offset: usize,
+    pub kind: ParseRangeErrorKind,
+}
+
+impl fmt::Display for ParseRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.offset, self.kind)
+    }
+}
+
+impl std::error::Error for ParseRangeError {}
+
+/// 입력을 바이트 위치와 함께 읽는 커서
+struct RangeCursor<'a> {
+    input: &'a str,
+    offset: usize,
+}
+
+impl RangeCursor<'_> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.offset..].chars().next()
+    }
+
+    fn error(&self, kind: ParseRangeErrorKind) -> ParseRangeError {
+        ParseRangeError {
+            offset: self.offset,
+            kind,
+        }
+    }
+
+    /// 공백과 줄바꿈을 건너뜀, 건너뛴 것이 있으면 true
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.offset;
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.offset += self.peek().map_or(0, char::len_utf8);
+        }
+        self.offset > start
+    }
+
+    /// 10진수 하나를 읽음
+    fn number<T: Id>(&mut self) -> Result<T, ParseRangeError> {
+        let start = self.offset;
+        let mut value = T::ZERO;
+        while let Some(digit) = self.peek().and_then(|c| c.to_digit(10)) {
+            value = value
+                .checked_mul(T::from(10))
+                .and_then(|v| v.checked_add(T::from(digit)))
+                .ok_or(ParseRangeError {
+                    offset: start,
+                    kind: ParseRangeErrorKind::NumberTooLarge,
+                })?;
+            self.offset += 1;
+        }
+        if self.offset == start {
+            return Err(self.error(ParseRangeErrorKind::ExpectedNumber(self.peek())));
+        }
+        Ok(value)
+    }
+}
+
+/// `11-22,95-115` 형식의 범위 목록을 파싱 (10진법)
+///
+/// 범위 사이에는 ',', 공백, 줄바꿈을 섞어 쓸 수 있고 마지막 ','는 무시.
+/// 첫 번째 잘못된 곳의 바이트 위치와 이유를 반환
+pub fn parse_ranges<T: Id>(input: &str) -> Result<Vec<(T, T)>, ParseRangeError> {
+    let mut cursor = RangeCursor { input, offset: 0 };
+    let mut ranges = Vec::new();
+
+    cursor.skip_whitespace();
+    while cursor.peek().is_some() {
+        let start = cursor.number()?;
+        if cursor.peek() != Some('-') {
+            return Err(cursor.error(ParseRangeErrorKind::ExpectedDash(cursor.peek())));
+        }
+        cursor.offset += 1;
+        let end = cursor.number()?;
+        ranges.push((start, end));
+
+        // 구분자: 공백, ',' 또는 둘 다
+        let spaced = cursor.skip_whitespace();
+        match cursor.peek() {
+            Some(',') => {
+                cursor.offset += 1;
+                cursor.skip_whitespace();
+            }
+            Some(c) if !spaced => {
+                return Err(cursor.error(ParseRangeErrorKind::ExpectedSeparator(c)));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(ranges)
+}
+
+// ========== 다음/이전 무효 ID ==========
+
+/// Part 1에서 n 이상인 가장 작은 무효 ID, u64 안에 없으면 None
+pub fn next_invalid_part1(n: u64) -> Option<u64> {
+    next_invalid_part1_radix(n, 10)
+}
+
+/// Part 1 다음 무효 ID (u64 또는 u128, radix 진법)
+pub fn next_invalid_part1_radix<T: Id>(n: T, radix: u32) -> Option<T> {
+    next_in_blocks(n, part1_blocks(radix))
+}
+
+/// Part 2에서 n 이상인 가장 작은 무효 ID, u64 안에 없으면 None
+pub fn next_invalid_part2(n: u64) -> Option<u64> {
+    next_invalid_part2_radix(n, 10)
+}
+
+/// Part 2 다음 무효 ID (u64 또는 u128, radix 진법)
+pub fn next_invalid_part2_radix<T: Id>(n: T, radix: u32) -> Option<T> {
+    next_in_blocks(n, part2_blocks(radix))
+}
+
+/// Part 1에서 n 이하인 가장 큰 무효 ID, 없으면 None
+pub fn prev_invalid_part1(n: u64) -> Option<u64> {
+    prev_invalid_part1_radix(n, 10)
+}
+
+/// Part 1 이전 무효 ID (u64 또는 u128, radix 진법)
+pub fn prev_invalid_part1_radix<T: Id>(n: T, radix: u32) -> Option<T> {
+    prev_in_blocks(n, part1_blocks(radix))
+}
+
+/// Part 2에서 n 이하인 가장 큰 무효 ID, 없으면 None
+pub fn prev_invalid_part2(n: u64) -> Option<u64> {
+    prev_invalid_part2_radix(n, 10)
+}
+
+/// Part 2 이전 무효 ID (u64 또는 u128, radix 진법)
+pub fn prev_invalid_part2_radix<T: Id>(n: T, radix: u32) -> Option<T> {
+    prev_in_blocks(n, part2_blocks(radix))
+}
+
+/// 블록마다 [n, T::MAX]의 가장 작은 패턴 ceil(n / multiplier)를 구해 최솟값
+/// 블록 수는 자릿수에 비례하므로 O(자릿수)
+fn next_in_blocks<T: Id>(n: T, blocks: impl Iterator<Item = Block<T>>) -> Option<T> {
+    blocks
+        .filter_map(|block| {
+            let (pattern, _) = block.patterns_in(n, T::MAX)?;
+            Some(pattern * block.multiplier)
+        })
+        .min()
+}
+
+/// 블록마다 [0, n]의 가장 큰 패턴 floor(n / multiplier)를 구해 최댓값
+fn prev_in_blocks<T: Id>(n: T, blocks: impl Iterator<Item = Block<T>>) -> Option<T> {
+    blocks
+        .filter_map(|block| {
+            let (_, pattern) = block.patterns_in(T::ZERO, n)?;
+            Some(pattern * block.multiplier)
+        })
+        .max()
 }
 
 #[cfg(test)]
@@ -172,13 +1205,7 @@ mod tests {
 
     #[test]
     fn test_optimized_vs_bruteforce() {
-        let ranges = [
-            (11, 22),
-            (95, 115),
-            (998, 1012),
-            (1, 1000),
-            (10000, 20000),
-        ];
+        let ranges = [(11, 22), (95, 115), (998, 1012), (1, 1000), (10000, 20000)];
         for (start, end) in ranges {
             assert_eq!(
                 sum_invalid_part1_bruteforce(start, end),
@@ -196,4 +1223,685 @@ mod tests {
             );
         }
     }
+
+    // ========== 진법 테스트 ==========
+
+    #[test]
+    fn test_is_invalid_radix() {
+        assert!(is_invalid_part1_radix(0b1010u64, 2));
+        assert!(is_invalid_part1_radix(0b11u64, 2));
+        assert!(!is_invalid_part1_radix(0b111u64, 2));
+        assert!(is_invalid_part1_radix(0xABABu64, 16));
+        assert!(!is_invalid_part1_radix(0xABBAu64, 16));
+        // 35 * 36 + 35 = "ZZ"
+        assert!(is_invalid_part1_radix(35 * 36 + 35u64, 36));
+
+        assert!(is_invalid_part2_radix(0b101010u64, 2));
+        assert!(is_invalid_part2_radix(0b111u64, 2));
+        assert!(!is_invalid_part2_radix(0b110u64, 2));
+        assert!(is_invalid_part2_radix(0xFFFu64, 16));
+        assert!(is_invalid_part2_radix(0x12121212u64, 16));
+        assert!(!is_invalid_part2_radix(0x1231u64, 16));
+
+        // 10진법에서는 무효지만 16진법에서는 아님: 1212 = 0x4BC
+        assert!(is_invalid_part1(1212));
+        assert!(!is_invalid_part1_radix(1212u64, 16));
+    }
+
+    #[test]
+    #[should_panic(expected = "진법")]
+    fn test_radix_out_of_range() {
+        is_invalid_part1_radix(5u64, 37);
+    }
+
+    #[test]
+    fn test_optimized_vs_bruteforce_every_radix() {
+        for radix in RADIX_RANGE {
+            let r = radix as u64;
+            let ranges = [(1, 2000), (r - 1, r * r + 1), (r * r / 2, r * r * r + 5)];
+            for (start, end) in ranges {
+                assert_eq!(
+                    sum_invalid_part1_bruteforce_radix(start, end, radix),
+                    sum_invalid_part1_optimized_radix(start, end, radix),
+                    "Part1 불일치: {}진법 범위 {}-{}",
+                    radix,
+                    start,
+                    end
+                );
+                assert_eq!(
+                    sum_invalid_part2_bruteforce_radix(start, end, radix),
+                    sum_invalid_part2_optimized_radix(start, end, radix),
+                    "Part2 불일치: {}진법 범위 {}-{}",
+                    radix,
+                    start,
+                    end
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_optimized_near_u64_max_radix() {
+        // 큰 값 근처에서도 승수 계산이 넘치지 않음
+        for radix in [2, 3, 10, 16, 36] {
+            let (start, end) = (u64::MAX - 10_000, u64::MAX);
+            assert_eq!(
+                sum_invalid_part1_bruteforce_radix(start, end, radix),
+                sum_invalid_part1_optimized_radix(start, end, radix),
+                "Part1 불일치: {}진법",
+                radix
+            );
+            assert_eq!(
+                sum_invalid_part2_bruteforce_radix(start, end, radix),
+                sum_invalid_part2_optimized_radix(start, end, radix),
+                "Part2 불일치: {}진법",
+                radix
+            );
+        }
+    }
+
+    // ========== 닫힌 형식 테스트 ==========
+
+    #[test]
+    fn test_mobius() {
+        let expected = [1, -1, -1, 0, -1, 1, -1, 0, 0, 1, -1, 0];
+        for (n, mu) in (1..).zip(expected) {
+            assert_eq!(mobius(n), mu, "μ({})", n);
+        }
+    }
+
+    #[test]
+    fn test_closed_form_vs_bruteforce_every_radix() {
+        for radix in RADIX_RANGE {
+            let r = radix as u64;
+            for (start, end) in [(1, 2000), (r * r / 2, r * r * r + 5)] {
+                assert_eq!(
+                    sum_invalid_part2_bruteforce_radix(start, end, radix),
+                    sum_invalid_part2_closed_form_radix(start, end, radix),
+                    "{}진법 범위 {}-{}",
+                    radix,
+                    start,
+                    end
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_closed_form_vs_optimized() {
+        let ranges = [
+            (1, 1_000_000_000),
+            (123_456_789, 98_765_432_101),
+            (10u64.pow(17), 10u64.pow(17) + 10u64.pow(9)),
+        ];
+        for (start, end) in ranges {
+            for radix in [2, 10, 16] {
+                assert_eq!(
+                    sum_invalid_part2_optimized_radix(start, end, radix),
+                    sum_invalid_part2_closed_form_radix(start, end, radix),
+                    "{}진법 범위 {}-{}",
+                    radix,
+                    start,
+                    end
+                );
+            }
+        }
+    }
+
+    // ========== u128 테스트 ==========
+
+    #[test]
+    fn test_u128_matches_u64() {
+        for radix in [2, 7, 10, 16, 36] {
+            for (start, end) in [(1u64, 5000u64), (95, 115), (123_456_789, 98_765_432_101)] {
+                let (wide_start, wide_end) = (start as u128, end as u128);
+                assert_eq!(
+                    checked_sum_invalid_part1(wide_start, wide_end, radix),
+                    checked_sum_invalid_part1(start, end, radix).map(u128::from),
+                    "Part1 {}진법 범위 {}-{}",
+                    radix,
+                    start,
+                    end
+                );
+                assert_eq!(
+                    checked_sum_invalid_part2(wide_start, wide_end, radix),
+                    checked_sum_invalid_part2(start, end, radix).map(u128::from),
+                    "Part2 {}진법 범위 {}-{}",
+                    radix,
+                    start,
+                    end
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_u128_beyond_20_digits() {
+        // 30자리: 15자리 패턴 2회
+        let part1_id: u128 = 123_456_789_012_345 * (10u128.pow(15) + 1);
+        // 39자리: 13자리 패턴 3회, u128::MAX보다 작음
+        let part2_id: u128 = 3_402_823_669_209 * (10u128.pow(26) + 10u128.pow(13) + 1);
+        assert!(part2_id < u128::MAX);
+        assert!(is_invalid_part1_radix(part1_id, 10));
+        assert!(!is_invalid_part1_radix(part2_id, 10));
+        assert!(is_invalid_part2_radix(part2_id, 10));
+
+        for id in [part1_id, part2_id] {
+            let (start, end) = (id - 2000, id + 2000);
+            assert_eq!(
+                checked_sum_invalid_part1(start, end, 10),
+                checked_sum_invalid_part1_bruteforce(start, end, 10)
+            );
+            assert_eq!(
+                checked_sum_invalid_part2(start, end, 10),
+                checked_sum_invalid_part2_bruteforce(start, end, 10)
+            );
+        }
+        assert_eq!(
+            checked_sum_invalid_part2(part2_id - 2000, part2_id + 2000, 10),
+            Some(part2_id)
+        );
+
+        // u128::MAX 근처에서도 승수 계산이 넘치지 않음
+        for radix in [2, 10, 36] {
+            let (start, end) = (u128::MAX - 3000, u128::MAX);
+            assert_eq!(
+                checked_sum_invalid_part2(start, end, radix),
+                checked_sum_invalid_part2_bruteforce(start, end, radix),
+                "{}진법",
+                radix
+            );
+        }
+    }
+
+    #[test]
+    fn test_checked_sum_overflow() {
+        // 1..=u64::MAX의 무효 ID 합은 u64를 넘지만 u128에는 들어감
+        assert_eq!(checked_sum_invalid_part1(1, u64::MAX, 10), None);
+        assert_eq!(checked_sum_invalid_part2(1, u64::MAX, 10), None);
+        let wide1 = checked_sum_invalid_part1(1, u64::MAX as u128, 10).unwrap();
+        let wide2 = checked_sum_invalid_part2(1, u64::MAX as u128, 10).unwrap();
+        assert!(wide1 > u64::MAX as u128);
+        assert!(wide2 > wide1);
+
+        // u128 전체 범위는 u128도 넘음
+        assert_eq!(checked_sum_invalid_part1(1, u128::MAX, 10), None);
+        assert_eq!(checked_sum_invalid_part2(1, u128::MAX, 2), None);
+
+        // 합이 딱 들어가는 경계: 11 + 22 + ... + 99 = 495
+        assert_eq!(checked_sum_invalid_part2(1u64, 99, 10), Some(495));
+    }
+
+    #[test]
+    #[should_panic(expected = "u64 범위를 넘음")]
+    fn test_u64_sum_panics_on_overflow() {
+        sum_invalid_part1_optimized(1, u64::MAX);
+    }
+
+    // ========== 나열 테스트 ==========
+
+    #[test]
+    fn test_invalid_ids_vs_bruteforce() {
+        for radix in [2, 3, 10, 16, 36] {
+            for (start, end) in [(0u64, 3000u64), (95, 115), (1000, 1000), (500, 20)] {
+                let ids1: Vec<u64> = invalid_ids_part1_radix(start, end, radix).collect();
+                let ids2: Vec<u64> = invalid_ids_part2_radix(start, end, radix).collect();
+                let expected1: Vec<u64> = (start..=end)
+                    .filter(|&n| is_invalid_part1_radix(n, radix))
+                    .collect();
+                let expected2: Vec<u64> = (start..=end)
+                    .filter(|&n| is_invalid_part2_radix(n, radix))
+                    .collect();
+                assert_eq!(ids1, expected1, "Part1 {}진법 {}-{}", radix, start, end);
+                assert_eq!(ids2, expected2, "Part2 {}진법 {}-{}", radix, start, end);
+
+                assert_eq!(
+                    count_invalid_part1_radix(start, end, radix),
+                    expected1.len() as u64
+                );
+                assert_eq!(
+                    count_invalid_part2_radix(start, end, radix),
+                    expected2.len() as u64
+                );
+                for n in 0..=expected2.len() as u64 {
+                    assert_eq!(
+                        nth_invalid_part2_radix(start, end, n, radix),
+                        expected2.get(n as usize).copied()
+                    );
+                }
+                for n in 0..=expected1.len() as u64 {
+                    assert_eq!(
+                        nth_invalid_part1_radix(start, end, n, radix),
+                        expected1.get(n as usize).copied()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_ids_huge_range() {
+        // 전체 u64 범위도 앞부분만 바로 나열
+        let first: Vec<u64> = invalid_ids_part2(1, u64::MAX).take(12).collect();
+        assert_eq!(
+            first,
+            vec![11, 22, 33, 44, 55, 66, 77, 88, 99, 111, 222, 333]
+        );
+        let first: Vec<u64> = invalid_ids_part1(1, u64::MAX).skip(8).take(3).collect();
+        assert_eq!(first, vec![99, 1010, 1111]);
+
+        // 1..10^18의 Part 1 개수: 9 + 90 + ... + 9 * 10^8
+        assert_eq!(count_invalid_part1(1, 10u64.pow(18)), 999_999_999);
+        assert_eq!(
+            nth_invalid_part1(1, 10u64.pow(18), 999_999_998),
+            Some(999_999_999_999_999_999)
+        );
+        assert_eq!(nth_invalid_part1(1, 10u64.pow(18), 999_999_999), None);
+
+        // 이분 탐색과 나열이 같은 ID를 줌
+        let (start, end) = (10u64.pow(11), 10u64.pow(17));
+        let nth = nth_invalid_part2(start, end, 1234).unwrap();
+        assert_eq!(invalid_ids_part2(start, end).nth(1234), Some(nth));
+        assert_eq!(count_invalid_part2(start, nth), 1235);
+
+        // 합계와 일관됨
+        let (start, end) = (123_456, 987_654_321);
+        assert_eq!(
+            invalid_ids_part2(start, end).sum::<u64>(),
+            sum_invalid_part2_closed_form(start, end)
+        );
+        assert_eq!(
+            invalid_ids_part2(start, end).count() as u64,
+            count_invalid_part2(start, end)
+        );
+    }
+
+    #[test]
+    fn test_invalid_ids_u128() {
+        let start = 10u128.pow(38);
+        let ids: Vec<u128> = invalid_ids_part2_radix(start, u128::MAX, 10)
+            .take(5)
+            .collect();
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+        assert!(ids.iter().all(|&id| is_invalid_part2_radix(id, 10)));
+        assert_eq!(
+            nth_invalid_part2_radix(start, u128::MAX, 0, 10),
+            Some(ids[0])
+        );
+        assert_eq!(
+            nth_invalid_part2_radix(start, u128::MAX, 4, 10),
+            Some(ids[4])
+        );
+    }
+
+    // ========== 여러 범위 테스트 ==========
+
+    #[test]
+    fn test_merge_ranges() {
+        assert_eq!(
+            merge_ranges(&[(50u64, 60u64), (1, 10), (5, 20), (21, 30), (40, 45), (9, 3)]),
+            vec![(1, 30), (40, 45), (50, 60)]
+        );
+        assert_eq!(merge_ranges(&[(1u64, 5u64), (2, 3)]), vec![(1, 5)]);
+        assert_eq!(
+            merge_ranges(&[(u64::MAX - 5, u64::MAX), (u64::MAX, u64::MAX), (0, 0)]),
+            vec![(0, 0), (u64::MAX - 5, u64::MAX)]
+        );
+        assert_eq!(merge_ranges::<u64>(&[]), vec![]);
+    }
+
+    #[test]
+    fn test_sum_over_overlapping_ranges() {
+        let ranges = [
+            (1u64, 1500u64),
+            (1000, 2500),
+            (95, 115),
+            (2400, 2400),
+            (4000, 3000),
+        ];
+        let union: HashSet<u64> = ranges
+            .iter()
+            .flat_map(|&(start, end)| start..=end)
+            .collect();
+        let expected1: u64 = union.iter().filter(|&&n| is_invalid_part1(n)).sum();
+        let expected2: u64 = union.iter().filter(|&&n| is_invalid_part2(n)).sum();
+
+        assert_eq!(sum_invalid_over_ranges(&ranges), (expected1, expected2));
+
+        // 범위별로 더하면 겹친 부분을 두 번 셈
+        let per_range: u64 = ranges
+            .iter()
+            .filter(|&&(start, end)| start <= end)
+            .map(|&(start, end)| sum_invalid_part2_closed_form(start, end))
+            .sum();
+        assert!(per_range > expected2);
+        let valid: Vec<(u64, u64)> = ranges
+            .iter()
+            .copied()
+            .filter(|&(start, end)| start <= end)
+            .collect();
+        assert_eq!(
+            checked_sum_per_range(&valid, |start, end| checked_sum_invalid_part2(
+                start, end, 10
+            )),
+            Some(per_range)
+        );
+
+        let wide: Vec<(u128, u128)> = ranges
+            .iter()
+            .map(|&(start, end)| (start as u128, end as u128))
+            .collect();
+        assert_eq!(
+            checked_sum_invalid_over_ranges(&wide, |start, end| {
+                checked_sum_invalid_part2(start, end, 10)
+            }),
+            Some(expected2 as u128)
+        );
+    }
+
+    // ========== 검사 규칙 테스트 ==========
+
+    /// 규칙의 합계/개수가 판정 함수로 하나씩 센 값과 같은지
+    fn check_rule<R: IdRule<u64>>(rule: &R, start: u64, end: u64) {
+        let ids: Vec<u64> = (start..=end).filter(|&n| rule.is_invalid(n)).collect();
+        assert_eq!(rule.checked_sum(start, end), Some(ids.iter().sum()));
+        assert_eq!(rule.count(start, end), ids.len() as u64);
+    }
+
+    #[test]
+    fn test_existing_rules_as_id_rule() {
+        for n in [11u64, 1010, 1111, 123123, 1698522, 111, 12] {
+            assert_eq!(RepeatedExactly::part1().is_invalid(n), is_invalid_part1(n));
+            assert_eq!(Repeated::part2().is_invalid(n), is_invalid_part2(n));
+        }
+        let (start, end) = (1u64, 10u64.pow(12));
+        assert_eq!(
+            RepeatedExactly::part1().checked_sum(start, end),
+            checked_sum_invalid_part1(start, end, 10)
+        );
+        assert_eq!(
+            Repeated::part2().checked_sum(start, end),
+            checked_sum_invalid_part2(start, end, 10)
+        );
+        assert_eq!(
+            IdRule::<u64>::count(&Repeated::part2(), start, end),
+            count_invalid_part2(start, end)
+        );
+    }
+
+    #[test]
+    fn test_repeated_exactly() {
+        let rule = RepeatedExactly::new(10, 3);
+        assert!(rule.is_invalid(777u64));
+        assert!(rule.is_invalid(123123123u64));
+        assert!(rule.is_invalid(111111u64)); // 11 × 3
+        assert!(!rule.is_invalid(1111u64));
+        assert!(!rule.is_invalid(123123u64));
+        for radix in [2, 10, 16] {
+            for repeats in 2..=5 {
+                check_rule(&RepeatedExactly::new(radix, repeats), 1, 5000);
+            }
+        }
+        check_rule(&RepeatedExactly::new(10, 3), 100_000, 200_000);
+    }
+
+    #[test]
+    fn test_palindrome_and_non_decreasing() {
+        let palindrome = Palindrome::new(10);
+        assert!(palindrome.is_invalid(11u64));
+        assert!(palindrome.is_invalid(90509u64));
+        assert!(!palindrome.is_invalid(7u64));
+        assert!(!palindrome.is_invalid(1231u64));
+        assert!(Palindrome::new(2).is_invalid(0b1001u64));
+
+        let non_decreasing = NonDecreasing::new(10);
+        assert!(non_decreasing.is_invalid(1229u64));
+        assert!(non_decreasing.is_invalid(5558u64));
+        assert!(!non_decreasing.is_invalid(5u64));
+        assert!(!non_decreasing.is_invalid(1210u64));
+        assert!(NonDecreasing::new(16).is_invalid(0x19afu64));
+
+        // 10..=99 중 회문 9개, 자릿수가 줄지 않는 수 45개
+        assert_eq!(palindrome.count(10u64, 99), 9);
+        assert_eq!(non_decreasing.count(10u64, 99), 45);
+        check_rule(&palindrome, 1, 3000);
+        check_rule(&non_decreasing, 1, 3000);
+    }
+
+    #[test]
+    fn test_rule_as_trait_object() {
+        let rules: Vec<Box<dyn IdRule<u128>>> = vec![
+            Box::new(RepeatedExactly::part1()),
+            Box::new(Repeated::part2()),
+            Box::new(Palindrome::new(10)),
+            Box::new(NonDecreasing::new(10)),
+        ];
+        let sums: Vec<Option<u128>> = rules.iter().map(|rule| rule.checked_sum(95, 115)).collect();
+        assert_eq!(
+            sums,
+            vec![
+                Some(99),
+                Some(99 + 111),
+                Some(99 + 101 + 111),
+                Some(99 + 111 + 112 + 113 + 114 + 115)
+            ]
+        );
+    }
+
+    // ========== 최소 주기별 조회 테스트 ==========
+
+    /// 브루트포스: 최소 주기 길이와 반복 횟수, 반복이 아니면 None
+    fn minimal_period(n: u64, radix: u32) -> Option<(u32, u32)> {
+        let s = digits(n, radix);
+        let len = s.len();
+        (1..len)
+            .filter(|&p| len.is_multiple_of(p))
+            .find(|&p| s[..p].repeat(len / p) == s)
+            .map(|p| (p as u32, (len / p) as u32))
+    }
+
+    #[test]
+    fn test_exact_period_vs_bruteforce() {
+        for radix in [2, 3, 10] {
+            let (start, end) = (1u64, 60_000u64);
+            let mut expected: std::collections::BTreeMap<(u32, u32), (u64, u64)> =
+                Default::default();
+            for n in start..=end {
+                if let Some(key) = minimal_period(n, radix) {
+                    let entry = expected.entry(key).or_default();
+                    entry.0 += 1;
+                    entry.1 += n;
+                }
+            }
+
+            let rows = period_breakdown(start, end, radix);
+            let actual: Vec<((u32, u32), (u64, u64))> = rows
+                .iter()
+                .map(|row| {
+                    (
+                        (row.pattern_len, row.repeats),
+                        (row.count, row.sum.unwrap()),
+                    )
+                })
+                .collect();
+            let expected_rows: Vec<_> = expected.clone().into_iter().collect();
+            assert_eq!(actual, expected_rows, "{}진법", radix);
+
+            for repeats in 2..=6 {
+                let (count, sum) = expected
+                    .iter()
+                    .filter(|((_, r), _)| *r == repeats)
+                    .fold((0, 0), |acc, (_, &(c, s))| (acc.0 + c, acc.1 + s));
+                assert_eq!(count_exact_repeats(start, end, radix, repeats), count);
+                assert_eq!(
+                    checked_sum_exact_repeats(start, end, radix, repeats),
+                    Some(sum)
+                );
+            }
+            for pattern_len in 1..=4 {
+                let (count, sum) = expected
+                    .iter()
+                    .filter(|((p, _), _)| *p == pattern_len)
+                    .fold((0, 0), |acc, (_, &(c, s))| (acc.0 + c, acc.1 + s));
+                assert_eq!(
+                    count_exact_pattern_len(start, end, radix, pattern_len),
+                    count
+                );
+                assert_eq!(
+                    checked_sum_exact_pattern_len(start, end, radix, pattern_len),
+                    Some(sum)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_breakdown_adds_up_to_part2() {
+        let (start, end) = (1u128, 10u128.pow(30));
+        let rows = period_breakdown(start, end, 10);
+        let count = rows.iter().fold(0, |total, row| total + row.count);
+        let sum = rows
+            .iter()
+            .try_fold(0u128, |total, row| total.checked_add(row.sum?));
+        assert_eq!(count, count_invalid_part2_radix(start, end, 10));
+        assert_eq!(sum, checked_sum_invalid_part2(start, end, 10));
+
+        // 111111은 1자리 패턴 6번으로만 셈
+        assert_eq!(count_exact_period(111_111u64, 111_111, 10, 1, 6), 1);
+        assert_eq!(count_exact_period(111_111u64, 111_111, 10, 2, 3), 0);
+        assert_eq!(count_exact_period(111_111u64, 111_111, 10, 3, 2), 0);
+    }
+
+    // ========== 범위 파싱 테스트 ==========
+
+    #[test]
+    fn test_parse_ranges() {
+        assert_eq!(
+            parse_ranges::<u64>("11-22,95-115"),
+            Ok(vec![(11, 22), (95, 115)])
+        );
+        // 공백, 줄바꿈, 마지막 ',' 허용
+        assert_eq!(
+            parse_ranges::<u64>("  11-22 ,\n 95-115\n998-1012\t,\r\n1-1,\n"),
+            Ok(vec![(11, 22), (95, 115), (998, 1012), (1, 1)])
+        );
+        assert_eq!(parse_ranges::<u64>(""), Ok(vec![]));
+        assert_eq!(parse_ranges::<u64>(" \n"), Ok(vec![]));
+
+        // u64를 넘는 39자리 ID
+        let big = "340282366920938463463374607431768211455";
+        assert_eq!(
+            parse_ranges::<u128>(&format!("1-{}", big)),
+            Ok(vec![(1, u128::MAX)])
+        );
+    }
+
+    #[test]
+    fn test_parse_ranges_errors() {
+        let error = |offset, kind| Err(ParseRangeError { offset, kind });
+        assert_eq!(
+            parse_ranges::<u64>("11-22,5-"),
+            error(8, ParseRangeErrorKind::ExpectedNumber(None))
+        );
+        assert_eq!(
+            parse_ranges::<u64>("11-22,,5-6"),
+            error(6, ParseRangeErrorKind::ExpectedNumber(Some(',')))
+        );
+        assert_eq!(
+            parse_ranges::<u64>("11-22\n95"),
+            error(8, ParseRangeErrorKind::ExpectedDash(None))
+        );
+        assert_eq!(
+            parse_ranges::<u64>("11+22"),
+            error(2, ParseRangeErrorKind::ExpectedDash(Some('+')))
+        );
+        assert_eq!(
+            parse_ranges::<u64>("11-22x"),
+            error(5, ParseRangeErrorKind::ExpectedSeparator('x'))
+        );
+        // 바이트 위치: '가'는 3바이트
+        assert_eq!(
+            parse_ranges::<u64>("가 1-2"),
+            error(0, ParseRangeErrorKind::ExpectedNumber(Some('가')))
+        );
+        assert_eq!(
+            parse_ranges::<u64>("1-2 가"),
+            error(4, ParseRangeErrorKind::ExpectedNumber(Some('가')))
+        );
+        assert_eq!(
+            parse_ranges::<u64>("1-99999999999999999999"),
+            error(2, ParseRangeErrorKind::NumberTooLarge)
+        );
+        assert_eq!(
+            ParseRangeError {
+                offset: 8,
+                kind: ParseRangeErrorKind::ExpectedNumber(None)
+            }
+            .to_string(),
+            "8: 숫자가 필요함: 입력 끝"
+        );
+    }
+
+    // ========== 다음/이전 무효 ID 테스트 ==========
+
+    #[test]
+    fn test_next_prev_vs_scan() {
+        for radix in [2, 3, 10, 16] {
+            let limit = 2000u64;
+            let invalid1: Vec<u64> = (0..=limit * 3)
+                .filter(|&n| is_invalid_part1_radix(n, radix))
+                .collect();
+            let invalid2: Vec<u64> = (0..=limit * 3)
+                .filter(|&n| is_invalid_part2_radix(n, radix))
+                .collect();
+            for n in 0..=limit {
+                let scan_next = |ids: &[u64]| ids.iter().copied().find(|&id| id >= n);
+                let scan_prev = |ids: &[u64]| ids.iter().copied().rev().find(|&id| id <= n);
+                assert_eq!(next_invalid_part1_radix(n, radix), scan_next(&invalid1));
+                assert_eq!(next_invalid_part2_radix(n, radix), scan_next(&invalid2));
+                assert_eq!(prev_invalid_part1_radix(n, radix), scan_prev(&invalid1));
+                assert_eq!(prev_invalid_part2_radix(n, radix), scan_prev(&invalid2));
+            }
+        }
+    }
+
+    #[test]
+    fn test_next_prev_large() {
+        // 가장 큰 Part 1 ID: 1844674407 × (10^10 + 1)
+        let largest = 18_446_744_071_844_674_407u64;
+        assert_eq!(prev_invalid_part1(u64::MAX), Some(largest));
+        assert_eq!(next_invalid_part1(largest), Some(largest));
+        assert_eq!(next_invalid_part1(largest + 1), None);
+        assert_eq!(prev_invalid_part1(10), None);
+        assert_eq!(prev_invalid_part2(0), None);
+
+        // 사이에 무효 ID가 없는지 닫힌 형식 개수로 확인
+        for n in [
+            1_000_000_007u64,
+            98_765_432_123_456,
+            12_345_678_901_234_567,
+            u64::MAX / 3,
+        ] {
+            let next = next_invalid_part2(n).unwrap();
+            assert!(is_invalid_part2(next) && next >= n);
+            assert_eq!(count_invalid_part2(n, next - 1), 0);
+            let prev = prev_invalid_part2(n).unwrap();
+            assert!(is_invalid_part2(prev) && prev <= n);
+            assert_eq!(count_invalid_part2(prev + 1, n), 0);
+
+            let next = next_invalid_part1(n).unwrap();
+            assert!(is_invalid_part1(next));
+            assert_eq!(count_invalid_part1(n, next - 1), 0);
+        }
+
+        // u128: 39자리
+        let n = 10u128.pow(38);
+        let next = next_invalid_part2_radix(n, 10).unwrap();
+        // 13자리 패턴 10^12를 3번 반복한 수가 100 × 13번보다 작음
+        assert_eq!(next, "1000000000000".repeat(3).parse::<u128>().unwrap());
+        assert_eq!(
+            prev_invalid_part2_radix(next - 1, 10),
+            prev_invalid_part2_radix(n, 10)
+        );
+    }
 }
@@ -1,31 +1,183 @@
-use day2::{sum_invalid_part1_optimized, sum_invalid_part2_optimized};
-use std::fs;
+use day2::{
+    IdRule, NonDecreasing, Palindrome, Repeated, RepeatedExactly, checked_sum_invalid_over_ranges,
+    checked_sum_invalid_part1, checked_sum_invalid_part2, checked_sum_per_range, merge_ranges,
+    parse_ranges, period_breakdown,
+};
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+
+/// --rule로 고를 수 있는 규칙 이름
+const RULE_NAMES: &str = "part1, part2, palindrome, nondecreasing, repeat<r>";
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("input.txt 읽기 실패");
-    let input = input.trim();
-
-    // 범위 파싱
-    let ranges: Vec<(u64, u64)> = input
-        .split(',')
-        .map(|range_str| {
-            let parts: Vec<&str> = range_str.split('-').collect();
-            let start: u64 = parts[0].parse().expect("시작 숫자 파싱 실패");
-            let end: u64 = parts[1].parse().expect("끝 숫자 파싱 실패");
-            (start, end)
-        })
-        .collect();
-
-    // 최적화 알고리즘 사용
-    let sum_part1: u64 = ranges
-        .iter()
-        .map(|&(start, end)| sum_invalid_part1_optimized(start, end))
-        .sum();
-    let sum_part2: u64 = ranges
-        .iter()
-        .map(|&(start, end)| sum_invalid_part2_optimized(start, end))
-        .sum();
-
-    println!("Part 1: {}", sum_part1);
-    println!("Part 2: {}", sum_part2);
+    // 인자: [--per-range] [--rule 이름] [--breakdown] [경로 | - | 범위...]
+    // 입력이 없으면 input.txt, -이면 stdin, 범위 목록인 인자는 그 자체로 입력
+    // --per-range: 겹치는 범위를 합치지 않고 범위별 합계를 그대로 더함
+    // --rule: 그 규칙 하나의 합계만 출력
+    // --breakdown: Part 2 무효 ID를 (패턴 길이, 반복 횟수)별로 나눈 표도 출력
+    let mut per_range = false;
+    let mut rule_name = None;
+    let mut breakdown = false;
+    let mut inputs = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--per-range" => per_range = true,
+            "--rule" => match args.next() {
+                Some(name) => rule_name = Some(name),
+                None => {
+                    eprintln!("--rule 뒤에 규칙 이름이 필요함 ({})", RULE_NAMES);
+                    std::process::exit(1);
+                }
+            },
+            "--breakdown" => breakdown = true,
+            _ if arg.starts_with("--") => {
+                eprintln!("알 수 없는 인자: {}", arg);
+                std::process::exit(1);
+            }
+            _ => inputs.push(arg),
+        }
+    }
+
+    let (source, input) = read_input(&inputs);
+
+    // 범위 파싱 (u128: 최대 39자리 ID)
+    let ranges: Vec<(u128, u128)> = match parse_ranges(&input) {
+        Ok(ranges) => ranges,
+        Err(error) => {
+            eprintln!("{}:{}", source, error);
+            std::process::exit(1);
+        }
+    };
+
+    // 기본: 정렬 후 겹치는 범위를 합쳐 같은 ID를 한 번만 셈
+    // --per-range: 합치지 않고 범위별 합계를 그대로 더함
+    let total = |sum: &dyn Fn(u128, u128) -> Option<u128>| {
+        if per_range {
+            checked_sum_per_range(&ranges, sum)
+        } else {
+            checked_sum_invalid_over_ranges(&ranges, sum)
+        }
+    };
+
+    if let Some(name) = rule_name {
+        let Some(rule) = rule_by_name(&name) else {
+            eprintln!("알 수 없는 규칙: {} ({})", name, RULE_NAMES);
+            std::process::exit(1);
+        };
+        match total(&|start, end| rule.checked_sum(start, end)) {
+            Some(sum) => println!("{}: {}", name, sum),
+            None => {
+                eprintln!("{} 합계가 u128 범위를 넘음", name);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // 최적화 알고리즘 사용, 합계가 u128을 넘으면 에러
+    let sum_part1 = total(&|start, end| checked_sum_invalid_part1(start, end, 10));
+    let sum_part2 = total(&|start, end| checked_sum_invalid_part2(start, end, 10));
+
+    match (sum_part1, sum_part2) {
+        (Some(sum_part1), Some(sum_part2)) => {
+            println!("Part 1: {}", sum_part1);
+            println!("Part 2: {}", sum_part2);
+            if breakdown {
+                if per_range {
+                    print_breakdown(&ranges);
+                } else {
+                    print_breakdown(&merge_ranges(&ranges));
+                }
+            }
+        }
+        (sum_part1, _) => {
+            let part = if sum_part1.is_none() { 1 } else { 2 };
+            eprintln!("Part {} 합계가 u128 범위를 넘음", part);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// 입력 출처 이름과 내용: 파일, stdin 또는 공백으로 이은 인자
+/// 숫자로 시작해도 있는 파일이면 파일로 읽음
+fn read_input(inputs: &[String]) -> (String, String) {
+    let result = match inputs {
+        [] => (
+            "input.txt".to_string(),
+            std::fs::read_to_string("input.txt"),
+        ),
+        [path] if path == "-" => {
+            let mut input = String::new();
+            (
+                "stdin".to_string(),
+                io::stdin().read_to_string(&mut input).map(|_| input),
+            )
+        }
+        // 숫자로 시작하는 파일 이름 (2025-input.txt 등)은 파일로 읽음
+        [path] if std::path::Path::new(path).is_file() => {
+            (path.clone(), std::fs::read_to_string(path))
+        }
+        // 없는 파일이고 숫자로 시작하면 범위 목록 (잘못되면 argv:<offset> 에러)
+        [first, ..] if first.starts_with(|c: char| c.is_ascii_digit()) => {
+            ("argv".to_string(), Ok(inputs.join(" ")))
+        }
+        [path] => (path.clone(), std::fs::read_to_string(path)),
+        _ => {
+            eprintln!("입력 파일은 하나만 지정 가능");
+            std::process::exit(1);
+        }
+    };
+    match result {
+        (source, Ok(input)) => (source, input),
+        (source, Err(error)) => {
+            eprintln!("{}: {}", source, error);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// --breakdown: 범위 전체를 (최소 주기 패턴 길이, 반복 횟수)별로 집계해 출력
+fn print_breakdown(ranges: &[(u128, u128)]) {
+    // (패턴 길이, 반복 횟수) -> (개수, 합계), 합계가 넘치면 None
+    let mut table: BTreeMap<(u32, u32), (u128, Option<u128>)> = BTreeMap::new();
+    for &(start, end) in ranges {
+        for row in period_breakdown(start, end, 10) {
+            let entry = table
+                .entry((row.pattern_len, row.repeats))
+                .or_insert((0, Some(0)));
+            entry.0 += row.count;
+            entry.1 = entry.1.zip(row.sum).and_then(|(a, b)| a.checked_add(b));
+        }
+    }
+
+    println!(
+        "{:>7} {:>7} {:>12} {:>40}",
+        "pattern", "repeats", "count", "sum"
+    );
+    for ((pattern_len, repeats), (count, sum)) in table {
+        let sum = sum.map_or("overflow".to_string(), |sum| sum.to_string());
+        println!(
+            "{:>7} {:>7} {:>12} {:>40}",
+            pattern_len, repeats, count, sum
+        );
+    }
+}
+
+/// 이름으로 규칙 선택, 모두 10진법
+/// repeat<r>: 정확히 r번 반복 (repeat2는 part1과 같음)
+fn rule_by_name(name: &str) -> Option<Box<dyn IdRule<u128>>> {
+    Some(match name {
+        "part1" => Box::new(RepeatedExactly::part1()),
+        "part2" => Box::new(Repeated::part2()),
+        "palindrome" => Box::new(Palindrome::new(10)),
+        "nondecreasing" => Box::new(NonDecreasing::new(10)),
+        _ => {
+            let repeats: u32 = name.strip_prefix("repeat")?.parse().ok()?;
+            if repeats < 2 {
+                return None;
+            }
+            Box::new(RepeatedExactly::new(10, repeats))
+        }
+    })
 }
@@ -0,0 +1,936 @@
+//! # Day 3: 배터리 뱅크 최대 전압(Joltage) 계산
+//!
+//! ## 문제 설명
+//! - 각 줄은 하나의 배터리 뱅크를 나타냄 (각 자리는 1-9 사이의 배터리 전압)
+//! - Part 1: 각 뱅크에서 정확히 2개의 배터리를 선택하여 2자리 수를 만듦
+//! - Part 2: 각 뱅크에서 정확히 12개의 배터리를 선택하여 12자리 수를 만듦
+//! - 배터리 순서는 유지해야 함 (재배열 불가)
+//! - 각 뱅크에서 만들 수 있는 최대 전압의 총합을 구함
+//! - k가 크면 (k > 19) 결과가 u64를 넘으므로 자릿수 문자열과 `BigUint`로 계산
+//! - `Bank`: 1-9가 아닌 문자나 k개보다 짧은 뱅크를 줄, 열과 함께 보고
+//!   (줄을 받는 함수들은 숫자가 아닌 문자를 건너뛰고 짧으면 0으로 처리)
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+
+/// Part 1 풀이: 각 뱅크에서 2개 배터리로 만들 수 있는 최대 전압의 합
+///
+/// # 예시
+/// - "987654321111111" → 98 (첫 두 배터리)
+/// - "811111111111119" → 89 (8과 9 선택)
+pub fn solve_part1(input: &str) -> u32 {
+    input.lines().map(max_joltage).sum()
+}
+
+/// Part 2 풀이: 각 뱅크에서 12개 배터리로 만들 수 있는 최대 전압의 합
+///
+/// # 예시
+/// - "987654321111111" → 987654321111
+/// - "234234234234278" → 434234234278 (앞의 2,3,2 제외)
+pub fn solve_part2(input: &str) -> BigUint {
+    solve_part2_k(input, 12)
+}
+
+/// 각 뱅크에서 k개 배터리로 만들 수 있는 최대 전압의 합, 자릿수 제한 없음
+pub fn solve_part2_k(input: &str, k: usize) -> BigUint {
+    input.lines().map(|line| max_joltage_k_big(line, k)).sum()
+}
+
+/// 선택한 배터리: 줄 안에서의 위치와 그 숫자로 만든 수
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    /// 선택한 문자의 위치 (0부터, 오름차순), 숫자가 아닌 문자도 위치에 포함
+    pub indices: Vec<usize>,
+    /// 선택한 숫자를 순서대로 이은 문자열 (k자리)
+    pub digits: String,
+}
+
+impl Selection {
+    /// 만든 수, u64를 넘으면 None
+    pub fn value(&self) -> Option<u64> {
+        self.digits.bytes().try_fold(0u64, |result, digit| {
+            result.checked_mul(10)?.checked_add((digit - b'0') as u64)
+        })
+    }
+
+    /// 만든 수, 자릿수 제한 없음
+    pub fn joltage(&self) -> BigUint {
+        BigUint::from_decimal(&self.digits).expect("선택한 문자는 모두 숫자")
+    }
+}
+
+/// 줄의 숫자와 그 위치: (문자 위치, 숫자), 숫자가 아닌 문자는 건너뜀
+fn digit_positions(line: &str) -> Vec<(usize, u32)> {
+    line.chars()
+        .enumerate()
+        .filter_map(|(pos, c)| c.to_digit(10).map(|digit| (pos, digit)))
+        .collect()
+}
+
+// ========== 배터리 뱅크 검증 ==========
+
+/// 뱅크 검증 에러 종류
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BankErrorKind {
+    /// 1-9가 아닌 문자
+    InvalidBattery(char),
+    /// 배터리가 필요한 개수보다 적음
+    TooShort { len: usize, required: usize },
+}
+
+impl fmt::Display for BankErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BankErrorKind::InvalidBattery(c) => {
+                write!(f, "배터리는 1-9 숫자여야 함: {:?}", c)
+            }
+            BankErrorKind::TooShort { len, required } => {
+                write!(f, "배터리 {}개, 최소 {}개 필요", len, required)
+            }
+        }
+    }
+}
+
+/// 뱅크 검증 에러 (줄 번호와 열 번호는 1부터, 열은 문자 단위)
+///
+/// 짧은 뱅크의 열은 줄 끝 다음 칸
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BankError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: BankErrorKind,
+}
+
+impl fmt::Display for BankError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl std::error::Error for BankError {}
+
+/// 배터리 뱅크: 배터리 전압과 줄 안에서의 위치
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bank {
+    /// (문자 위치, 전압)
+    batteries: Vec<(usize, u32)>,
+}
+
+impl Bank {
+    /// 한 줄을 검증: 모든 문자가 1-9이고 배터리가 required개 이상이어야 함
+    /// (에러의 줄 번호는 1, `parse_banks`가 실제 줄 번호로 바꿈)
+    pub fn parse(line: &str, required: usize) -> Result<Bank, BankError> {
+        let mut batteries = Vec::with_capacity(line.len());
+        for (pos, c) in line.chars().enumerate() {
+            match c.to_digit(10) {
+                Some(digit) if digit > 0 => batteries.push((pos, digit)),
+                _ => {
+                    return Err(BankError {
+                        line: 1,
+                        column: pos + 1,
+                        kind: BankErrorKind::InvalidBattery(c),
+                    });
+                }
+            }
+        }
+        if batteries.len() < required {
+            return Err(BankError {
+                line: 1,
+                column: batteries.len() + 1,
+                kind: BankErrorKind::TooShort {
+                    len: batteries.len(),
+                    required,
+                },
+            });
+        }
+        Ok(Bank { batteries })
+    }
+
+    /// 검증 없이: 숫자가 아닌 문자는 건너뛰고 길이도 확인하지 않음 (--lenient)
+    pub fn lenient(line: &str) -> Bank {
+        Bank {
+            batteries: digit_positions(line),
+        }
+    }
+
+    /// 배터리 개수
+    pub fn len(&self) -> usize {
+        self.batteries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.batteries.is_empty()
+    }
+
+    /// k개 배터리로 만드는 최대 전압 선택 (`select_joltage_k`), 배터리가 k개 미만이면 None
+    pub fn select(&self, k: usize) -> Option<Selection> {
+        select_k(&self.batteries, k)
+    }
+
+    /// 2개 배터리로 만드는 최대 전압 선택 (`select_joltage`), 배터리가 2개 미만이면 None
+    pub fn select_pair(&self) -> Option<Selection> {
+        select_pair(&self.batteries)
+    }
+}
+
+/// 모든 줄을 검증해 뱅크로, 줄마다 결과를 반환 (에러는 실제 줄 번호)
+pub fn parse_banks(
+    input: &str,
+    required: usize,
+) -> impl Iterator<Item = Result<Bank, BankError>> + '_ {
+    input.lines().enumerate().map(move |(i, line)| {
+        Bank::parse(line, required).map_err(|error| BankError {
+            line: i + 1,
+            ..error
+        })
+    })
+}
+
+/// Part 1 합계: 뱅크마다 2개 배터리의 최대 전압, 2개 미만인 뱅크는 0
+pub fn total_part1(banks: &[Bank]) -> u32 {
+    banks
+        .iter()
+        .filter_map(|bank| bank.select_pair()?.value())
+        .map(|value| value as u32)
+        .sum()
+}
+
+/// Part 2 합계: 뱅크마다 k개 배터리의 최대 전압, k개 미만인 뱅크는 0
+pub fn total_part2(banks: &[Bank], k: usize) -> BigUint {
+    banks
+        .iter()
+        .filter_map(|bank| bank.select(k))
+        .map(|selection| selection.joltage())
+        .sum()
+}
+
+// ========== 배터리 선택 ==========
+
+/// (위치, 숫자) 목록으로 Selection 생성
+fn selection_from(chosen: &[(usize, u32)]) -> Selection {
+    Selection {
+        indices: chosen.iter().map(|&(pos, _)| pos).collect(),
+        digits: chosen
+            .iter()
+            .map(|&(_, digit)| char::from_digit(digit, 10).unwrap())
+            .collect(),
+    }
+}
+
+/// 문자열에서 k개의 숫자를 순서대로 선택하여 만들 수 있는 최대 수를 반환
+///
+/// # 알고리즘: 단조 스택(Monotonic Stack)
+///
+/// 버릴 수 있는 숫자는 n - k개.
+/// 숫자를 앞에서부터 스택에 쌓되, 새 숫자가 스택 top보다 크고 아직 버릴 수 있으면
+/// top을 버림 (더 큰 숫자를 앞자리로 당기는 것이 항상 이득).
+/// 각 숫자는 한 번 쌓이고 최대 한 번 버려지므로 O(n).
+/// 끝까지 버릴 기회를 다 쓰지 못했으면 스택의 앞 k개가 답.
+///
+/// ## 예시: "234234234234278"에서 k=12개 선택 (버릴 수 있는 개수 3)
+///
+/// ```text
+/// 2       → [2]
+/// 3       → 2 버림 → [3]
+/// 4       → 3 버림 → [4]       (버릴 수 있는 개수 1 남음)
+/// 2       → [4, 2]
+/// 3       → 2 버림 → [4, 3]    (버릴 수 있는 개수 0)
+/// ... 이후는 그대로 쌓음 ...
+/// 결과: 434234234278
+/// ```
+///
+/// # Arguments
+/// * `line` - 배터리 뱅크를 나타내는 문자열 (각 문자는 1-9 사이의 숫자)
+/// * `k` - 선택할 배터리 개수
+///
+/// # Returns
+/// 선택한 k개의 숫자로 만든 최대 수 (u64), 숫자가 k개 미만이면 0
+///
+/// # Panics
+/// 결과가 u64를 넘으면 (k > 19일 때 가능) panic, 큰 k는 `max_joltage_k_big` 사용
+pub fn max_joltage_k(line: &str, k: usize) -> u64 {
+    select_joltage_k(line, k).map_or(0, |selection| {
+        selection
+            .value()
+            .expect("전압이 u64 범위를 넘음, max_joltage_k_big 사용")
+    })
+}
+
+/// `max_joltage_k`의 자릿수 제한 없는 버전, 숫자가 k개 미만이면 0
+pub fn max_joltage_k_big(line: &str, k: usize) -> BigUint {
+    select_joltage_k(line, k).map_or(BigUint::default(), |selection| selection.joltage())
+}
+
+/// `max_joltage_k`와 같은 선택을 하되 선택한 위치도 반환, 숫자가 k개 미만이면 None
+///
+/// 같은 숫자가 여러 개면 앞쪽 것을 선택
+pub fn select_joltage_k(line: &str, k: usize) -> Option<Selection> {
+    Bank::lenient(line).select(k)
+}
+
+/// 단조 스택으로 k개 선택, digits는 (위치, 숫자)
+fn select_k(digits: &[(usize, u32)], k: usize) -> Option<Selection> {
+    // 숫자가 k개 미만이면 선택 불가
+    if digits.len() < k {
+        return None;
+    }
+
+    // 아직 버릴 수 있는 숫자 개수
+    let mut drops = digits.len() - k;
+    // (위치, 숫자)
+    let mut stack: Vec<(usize, u32)> = Vec::with_capacity(digits.len());
+    for &(pos, digit) in digits {
+        // 더 작은 top을 버리고 현재 숫자를 앞으로 당김
+        while drops > 0 && stack.last().is_some_and(|&(_, top)| top < digit) {
+            stack.pop();
+            drops -= 1;
+        }
+        stack.push((pos, digit));
+    }
+
+    // 버리지 못한 나머지는 뒤에서 잘라냄 (뒤쪽이 더 작거나 같음)
+    stack.truncate(k);
+    Some(selection_from(&stack))
+}
+
+/// `max_joltage_k`의 참조 구현: 자리마다 선택 가능한 범위를 다시 훑음, O(n·k)
+/// u64로 누적하므로 k ≤ 19에서만 사용
+///
+/// # 알고리즘: 그리디(Greedy) 선택법
+///
+/// k자리 수를 만들 때, 앞자리가 클수록 전체 수가 커짐.
+/// 따라서 각 자리마다 선택 가능한 범위 내에서 가장 큰 숫자를 선택.
+///
+/// ## 선택 가능한 범위 계산
+/// - i번째 자리(0-indexed)를 선택할 때
+/// - 시작 위치: 이전에 선택한 위치 + 1
+/// - 끝 위치: n - (남은 자리수) = n - (k - i - 1)
+///   - 끝 위치 이후에 최소 (k - i - 1)개의 숫자가 남아있어야 함
+///
+/// ## 예시: "234234234234278" (n=15)에서 k=12개 선택
+///
+/// ```text
+/// 인덱스:  0 1 2 3 4 5 6 7 8 9 10 11 12 13 14
+/// 숫자:    2 3 4 2 3 4 2 3 4 2  3  4  2  7  8
+///
+/// 1번째 자리: 범위 [0, 3], 숫자 2,3,4,2 → 최대 4 (위치 2)
+/// 2번째 자리: 범위 [3, 4], 숫자 2,3 → 최대 3 (위치 4)
+/// 3번째 자리: 범위 [5, 5], 숫자 4 → 4 (위치 5)
+/// ... 이하 생략 ...
+/// 결과: 434234234278
+/// ```
+///
+/// # Arguments
+/// * `line` - 배터리 뱅크를 나타내는 문자열 (각 문자는 1-9 사이의 숫자)
+/// * `k` - 선택할 배터리 개수
+///
+/// # Returns
+/// 선택한 k개의 숫자로 만든 최대 수 (u64)
+pub fn max_joltage_k_rescan(line: &str, k: usize) -> u64 {
+    // 문자열에서 숫자만 추출하여 벡터로 변환
+    // filter_map: 숫자가 아닌 문자는 None으로 필터링됨
+    let digits: Vec<u64> = line
+        .chars()
+        .filter_map(|c| c.to_digit(10).map(|d| d as u64))
+        .collect();
+
+    // 숫자가 k개 미만이면 선택 불가
+    if digits.len() < k {
+        return 0;
+    }
+
+    let n = digits.len();
+    let mut result: u64 = 0; // 최종 결과 (k자리 수)
+    let mut start = 0; // 현재 선택 가능한 시작 위치
+
+    // k개의 자리를 순서대로 채움
+    for i in 0..k {
+        // i번째 자리에서 선택 가능한 끝 위치 계산
+        // 남은 자리수: k - i - 1 (현재 자리 제외)
+        // 끝 위치 이후에 최소 (k - i - 1)개가 남아야 하므로
+        // end = n - (k - i - 1)
+        let end = n - (k - i - 1);
+
+        // [start, end) 범위에서 최대 숫자와 그 위치 찾기
+        let mut max_digit = 0;
+        let mut max_pos = start;
+        for (pos, &digit) in digits.iter().enumerate().take(end).skip(start) {
+            if digit > max_digit {
+                max_digit = digit;
+                max_pos = pos;
+            }
+        }
+
+        // 결과에 현재 자리 추가 (10을 곱해서 자리수 이동 후 더함)
+        result = result * 10 + max_digit;
+
+        // 다음 선택은 현재 위치 다음부터 시작
+        start = max_pos + 1;
+    }
+
+    result
+}
+
+/// Part 1용: 2개의 숫자를 선택하여 만들 수 있는 최대 2자리 수를 반환
+///
+/// # 알고리즘: Suffix Maximum 배열 활용
+///
+/// 2자리 수 XY를 최대화하려면:
+/// - X(십의 자리)가 더 중요 → 가능한 큰 X 선택
+/// - 각 위치 i에서 X를 선택하면, Y는 i 이후의 최대값이어야 함
+///
+/// ## Suffix Maximum 배열
+/// - suffix_max[i] = max(digits[i], digits[i+1], ..., digits[n-1])
+/// - 위치 i 이후(포함)의 최대값을 미리 계산
+///
+/// ## 최대 전압 계산
+/// - 각 위치 i (0 ~ n-2)에서: digits[i] * 10 + suffix_max[i+1]
+/// - 이 중 최대값이 정답
+///
+/// # 예시: "818181911112111"
+///
+/// ```text
+/// 인덱스:     0 1 2 3 4 5 6 7 8 9 10 11 12 13 14
+/// 숫자:       8 1 8 1 8 1 9 1 1 1  1  2  1  1  1
+/// suffix_max: 9 9 9 9 9 9 9 2 2 2  2  2  1  1  1
+///
+/// 위치 6에서: 9 * 10 + 2 = 92 (최대!)
+/// ```
+///
+/// # Arguments
+/// * `line` - 배터리 뱅크를 나타내는 문자열
+///
+/// # Returns
+/// 선택한 2개의 숫자로 만든 최대 2자리 수 (u32)
+pub fn max_joltage(line: &str) -> u32 {
+    select_joltage(line)
+        .and_then(|selection| selection.value())
+        .map_or(0, |value| value as u32)
+}
+
+/// `max_joltage`와 같은 선택을 하되 선택한 두 위치도 반환, 숫자가 2개 미만이면 None
+///
+/// 같은 값을 만드는 선택이 여러 개면 가장 앞쪽 것을 선택
+pub fn select_joltage(line: &str) -> Option<Selection> {
+    Bank::lenient(line).select_pair()
+}
+
+/// suffix maximum으로 2개 선택, digits는 (위치, 숫자)
+fn select_pair(digits: &[(usize, u32)]) -> Option<Selection> {
+    // 최소 2개의 숫자가 필요
+    if digits.len() < 2 {
+        return None;
+    }
+
+    // suffix_max 배열 구축: suffix_max[i] = digits[i..]에서 최대값의 인덱스
+    // 뒤에서부터 순회하며 계산 (동적 프로그래밍), 같은 값이면 앞쪽 인덱스
+    let mut suffix_max = vec![0usize; digits.len()];
+    suffix_max[digits.len() - 1] = digits.len() - 1; // 마지막 원소 초기화
+
+    // 뒤에서 앞으로 순회하며 suffix_max 계산
+    for i in (0..digits.len() - 1).rev() {
+        let next = suffix_max[i + 1];
+        suffix_max[i] = if digits[i].1 >= digits[next].1 {
+            i
+        } else {
+            next
+        };
+    }
+
+    // 모든 가능한 첫 번째 위치에서 최대 전압 계산
+    // 위치 i를 첫 번째 숫자로 선택하면:
+    // - 십의 자리: digits[i]
+    // - 일의 자리: digits[suffix_max[i+1]] (i 이후의 최대값)
+    let mut best: Option<(u32, usize, usize)> = None;
+    for i in 0..digits.len() - 1 {
+        let j = suffix_max[i + 1];
+        let value = digits[i].1 * 10 + digits[j].1;
+        if best.is_none_or(|(best_value, _, _)| value > best_value) {
+            best = Some((value, i, j));
+        }
+    }
+
+    best.map(|(_, i, j)| selection_from(&[digits[i], digits[j]]))
+}
+
+/// 선택한 위치의 문자를 open과 close로 감싸 강조한 줄
+///
+/// # 예시
+/// - highlight("818181911112111", &[6, 11], "[", "]") → "818181[9]1111[2]111"
+pub fn highlight(line: &str, indices: &[usize], open: &str, close: &str) -> String {
+    let mut selected = indices.iter().peekable();
+    let mut result = String::with_capacity(line.len() + indices.len() * (open.len() + close.len()));
+    for (pos, c) in line.chars().enumerate() {
+        if selected.next_if_eq(&&pos).is_some() {
+            result.push_str(open);
+            result.push(c);
+            result.push_str(close);
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+// ========== 임의 자릿수 정수 ==========
+
+/// 10^9 진법 limb 하나의 크기
+const LIMB_BASE: u32 = 1_000_000_000;
+/// limb 하나의 10진법 자릿수
+const LIMB_DIGITS: usize = 9;
+
+/// 자릿수 제한 없는 부호 없는 정수 (덧셈만 지원)
+///
+/// 10^9 진법 limb를 낮은 자리부터 저장, 맨 위 limb는 0이 아님 (0은 빈 벡터)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    /// 10진법 문자열에서 생성, 숫자가 아닌 문자가 있으면 None (빈 문자열은 0)
+    pub fn from_decimal(digits: &str) -> Option<Self> {
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        // 뒤에서부터 9자리씩 잘라 limb 하나로
+        let limbs = digits
+            .as_bytes()
+            .rchunks(LIMB_DIGITS)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .fold(0u32, |limb, &b| limb * 10 + (b - b'0') as u32)
+            })
+            .collect();
+        let mut result = BigUint { limbs };
+        result.normalize();
+        Some(result)
+    }
+
+    /// u64로 변환, 넘으면 None
+    pub fn to_u64(&self) -> Option<u64> {
+        self.limbs.iter().rev().try_fold(0u64, |result, &limb| {
+            result
+                .checked_mul(LIMB_BASE as u64)?
+                .checked_add(limb as u64)
+        })
+    }
+
+    /// 맨 위의 0 limb 제거
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(mut value: u64) -> Self {
+        let mut limbs = Vec::new();
+        while value > 0 {
+            limbs.push((value % LIMB_BASE as u64) as u32);
+            value /= LIMB_BASE as u64;
+        }
+        BigUint { limbs }
+    }
+}
+
+impl AddAssign<&BigUint> for BigUint {
+    fn add_assign(&mut self, other: &BigUint) {
+        if self.limbs.len() < other.limbs.len() {
+            self.limbs.resize(other.limbs.len(), 0);
+        }
+        let mut carry = 0;
+        for (i, limb) in self.limbs.iter_mut().enumerate() {
+            let sum = *limb + other.limbs.get(i).copied().unwrap_or(0) + carry;
+            // 두 limb와 올림의 합은 2·10^9보다 작아 u32에 들어감
+            (*limb, carry) = if sum >= LIMB_BASE {
+                (sum - LIMB_BASE, 1)
+            } else {
+                (sum, 0)
+            };
+            if carry == 0 && i >= other.limbs.len() {
+                break;
+            }
+        }
+        if carry > 0 {
+            self.limbs.push(carry);
+        }
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+
+    fn add(mut self, other: BigUint) -> BigUint {
+        self += &other;
+        self
+    }
+}
+
+impl Sum for BigUint {
+    fn sum<I: Iterator<Item = BigUint>>(iter: I) -> BigUint {
+        iter.fold(BigUint::default(), |total, value| total + value)
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Some((top, rest)) = self.limbs.split_last() else {
+            return write!(f, "0");
+        };
+        write!(f, "{}", top)?;
+        for limb in rest.iter().rev() {
+            write!(f, "{:09}", limb)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ==================== Part 1 테스트 ====================
+
+    #[test]
+    fn test_max_joltage_example1() {
+        // "987654321111111"에서 첫 두 숫자 9,8 선택 → 98
+        assert_eq!(max_joltage("987654321111111"), 98);
+    }
+
+    #[test]
+    fn test_max_joltage_example2() {
+        // "811111111111119"에서 8과 마지막 9 선택 → 89
+        assert_eq!(max_joltage("811111111111119"), 89);
+    }
+
+    #[test]
+    fn test_max_joltage_example3() {
+        // "234234234234278"에서 마지막 7,8 선택 → 78
+        assert_eq!(max_joltage("234234234234278"), 78);
+    }
+
+    #[test]
+    fn test_max_joltage_example4() {
+        // "818181911112111"에서 9와 2 선택 → 92
+        assert_eq!(max_joltage("818181911112111"), 92);
+    }
+
+    #[test]
+    fn test_select_joltage_indices() {
+        // 9 (위치 6)와 2 (위치 11)
+        let selection = select_joltage("818181911112111").unwrap();
+        assert_eq!(selection.indices, vec![6, 11]);
+        assert_eq!(selection.digits, "92");
+        assert_eq!(selection.value(), Some(92));
+        // 같은 값이면 가장 앞쪽: 9 (위치 0)와 그 뒤 첫 9 (위치 2)
+        assert_eq!(select_joltage("91919").unwrap().indices, vec![0, 2]);
+        // 위치는 숫자가 아닌 문자도 포함해서 셈
+        assert_eq!(select_joltage("1 2").unwrap().indices, vec![0, 2]);
+        assert_eq!(select_joltage("7"), None);
+    }
+
+    #[test]
+    fn test_solve_part1_example() {
+        // 전체 예시: 98 + 89 + 78 + 92 = 357
+        let input = "987654321111111\n811111111111119\n234234234234278\n818181911112111";
+        assert_eq!(solve_part1(input), 357);
+    }
+
+    // ==================== Part 2 테스트 ====================
+
+    #[test]
+    fn test_max_joltage_k_example1() {
+        // "987654321111111"에서 12개 선택 → 987654321111
+        // 끝의 1 세 개만 제외
+        assert_eq!(max_joltage_k("987654321111111", 12), 987654321111);
+    }
+
+    #[test]
+    fn test_max_joltage_k_example2() {
+        // "811111111111119"에서 12개 선택 → 811111111119
+        // 중간의 1 세 개 제외, 8과 마지막 9는 반드시 포함
+        assert_eq!(max_joltage_k("811111111111119", 12), 811111111119);
+    }
+
+    #[test]
+    fn test_max_joltage_k_example3() {
+        // "234234234234278"에서 12개 선택 → 434234234278
+        // 앞의 2,3,2 제외하고 4부터 시작
+        assert_eq!(max_joltage_k("234234234234278", 12), 434234234278);
+    }
+
+    #[test]
+    fn test_max_joltage_k_example4() {
+        // "818181911112111"에서 12개 선택 → 888911112111
+        // 앞쪽의 1들을 제외하고 8들과 9를 최대한 포함
+        assert_eq!(max_joltage_k("818181911112111", 12), 888911112111);
+    }
+
+    #[test]
+    fn test_select_joltage_k_indices() {
+        // 앞의 2,3,2 (위치 0, 1, 3) 제외
+        let selection = select_joltage_k("234234234234278", 12).unwrap();
+        assert_eq!(
+            selection.indices,
+            vec![2, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]
+        );
+        assert_eq!(selection.value(), Some(434234234278));
+        // 같은 숫자는 앞쪽 것을 선택
+        assert_eq!(select_joltage_k("99999", 3).unwrap().indices, vec![0, 1, 2]);
+        assert_eq!(select_joltage_k("123", 4), None);
+    }
+
+    #[test]
+    fn test_highlight() {
+        assert_eq!(
+            highlight("818181911112111", &[6, 11], "[", "]"),
+            "818181[9]1111[2]111"
+        );
+        assert_eq!(highlight("123", &[], "[", "]"), "123");
+        assert_eq!(highlight("123", &[0, 1, 2], "<", ">"), "<1><2><3>");
+    }
+
+    #[test]
+    fn test_max_joltage_k_rescan_examples() {
+        // 참조 구현도 같은 예시 결과
+        assert_eq!(max_joltage_k_rescan("987654321111111", 12), 987654321111);
+        assert_eq!(max_joltage_k_rescan("811111111111119", 12), 811111111119);
+        assert_eq!(max_joltage_k_rescan("234234234234278", 12), 434234234278);
+        assert_eq!(max_joltage_k_rescan("818181911112111", 12), 888911112111);
+    }
+
+    #[test]
+    fn test_max_joltage_k_edge_cases() {
+        // 숫자가 k개 미만이면 0, k = 0이면 빈 선택이므로 0, k = n이면 전부 선택
+        assert_eq!(max_joltage_k("123", 4), 0);
+        assert_eq!(max_joltage_k("123", 0), 0);
+        assert_eq!(max_joltage_k("123", 3), 123);
+        // 같은 숫자는 버리지 않음
+        assert_eq!(max_joltage_k("99999", 3), 999);
+        // 감소하는 뱅크는 끝에서 잘라냄
+        assert_eq!(max_joltage_k("987654321", 4), 9876);
+    }
+
+    /// 테스트용 의사 난수 생성기 (xorshift64)
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        /// [0, bound) 범위의 수
+        fn below(&mut self, bound: u64) -> u64 {
+            self.next() % bound
+        }
+    }
+
+    /// 1-9 숫자로 이루어진 길이 len의 무작위 뱅크
+    fn random_bank(rng: &mut Rng, len: usize, max_digit: u64) -> String {
+        (0..len)
+            .map(|_| char::from_digit(1 + rng.below(max_digit) as u32, 10).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_max_joltage_k_matches_rescan() {
+        // 무작위 뱅크에서 스택 구현과 참조 구현 비교
+        // 숫자 종류를 줄인 경우도 섞어 같은 숫자가 많이 나오게 함
+        // k ≤ 19: u64를 넘지 않는 범위, k > len인 경우도 포함
+        let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+        for _ in 0..5000 {
+            let len = rng.below(40) as usize;
+            let max_digit = 1 + rng.below(9);
+            let bank = random_bank(&mut rng, len, max_digit);
+            let k = rng.below(len as u64 + 2).min(19) as usize;
+            assert_eq!(
+                max_joltage_k(&bank, k),
+                max_joltage_k_rescan(&bank, k),
+                "bank = {}, k = {}",
+                bank,
+                k
+            );
+            // 선택한 위치의 숫자를 이으면 같은 값
+            if let Some(selection) = select_joltage_k(&bank, k) {
+                let chosen: String = selection.indices.iter().map(|&i| &bank[i..=i]).collect();
+                assert_eq!(chosen, selection.digits);
+                assert!(selection.indices.windows(2).all(|w| w[0] < w[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_max_joltage_k_matches_rescan_long() {
+        // 긴 뱅크에서도 같은 결과
+        let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+        for _ in 0..50 {
+            let len = 1000 + rng.below(1000) as usize;
+            let max_digit = 1 + rng.below(9);
+            let bank = random_bank(&mut rng, len, max_digit);
+            for k in [1, 2, 12, 19] {
+                assert_eq!(
+                    max_joltage_k(&bank, k),
+                    max_joltage_k_rescan(&bank, k),
+                    "k = {}",
+                    k
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_part2_example() {
+        // 전체 예시 합계:
+        // 987654321111 + 811111111119 + 434234234278 + 888911112111 = 3121910778619
+        let input = "987654321111111\n811111111111119\n234234234234278\n818181911112111";
+        assert_eq!(solve_part2(input), BigUint::from(3121910778619));
+    }
+
+    // ==================== 뱅크 검증 테스트 ====================
+
+    #[test]
+    fn test_bank_parse_valid() {
+        let bank = Bank::parse("818181911112111", 12).unwrap();
+        assert_eq!(bank.len(), 15);
+        assert_eq!(bank.select(12).unwrap().digits, "888911112111");
+        assert_eq!(bank.select_pair().unwrap().digits, "92");
+    }
+
+    #[test]
+    fn test_bank_parse_invalid_battery() {
+        // 숫자가 아닌 문자와 0은 배터리가 아님, 열은 1부터
+        let error = Bank::parse("12x4", 2).unwrap_err();
+        assert_eq!(error.column, 3);
+        assert_eq!(error.kind, BankErrorKind::InvalidBattery('x'));
+        let error = Bank::parse("1204", 2).unwrap_err();
+        assert_eq!(error.kind, BankErrorKind::InvalidBattery('0'));
+        let error = Bank::parse("12 ", 2).unwrap_err();
+        assert_eq!(
+            (error.column, error.kind),
+            (3, BankErrorKind::InvalidBattery(' '))
+        );
+    }
+
+    #[test]
+    fn test_bank_parse_too_short() {
+        let error = Bank::parse("987", 12).unwrap_err();
+        assert_eq!(error.column, 4);
+        assert_eq!(
+            error.kind,
+            BankErrorKind::TooShort {
+                len: 3,
+                required: 12
+            }
+        );
+        assert_eq!(error.to_string(), "1:4: 배터리 3개, 최소 12개 필요");
+        // 빈 줄도 짧은 뱅크
+        assert!(Bank::parse("", 2).is_err());
+    }
+
+    #[test]
+    fn test_parse_banks_line_numbers() {
+        let input = "987654321111111\n81111a111111119\n234";
+        let results: Vec<_> = parse_banks(input, 12).collect();
+        assert!(results[0].is_ok());
+        let error = results[1].as_ref().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+        assert_eq!(error.to_string(), "2:6: 배터리는 1-9 숫자여야 함: 'a'");
+        let error = results[2].as_ref().unwrap_err();
+        assert_eq!((error.line, error.column), (3, 4));
+    }
+
+    #[test]
+    fn test_bank_lenient_matches_line_functions() {
+        // lenient는 기존 줄 함수와 같은 동작: 숫자가 아닌 문자를 건너뛰고, 짧으면 0
+        let input = "98x7654321111111\n811111111111119\n12";
+        let banks: Vec<Bank> = input.lines().map(Bank::lenient).collect();
+        assert_eq!(total_part1(&banks), solve_part1(input));
+        assert_eq!(total_part2(&banks, 12), solve_part2(input));
+        // 위치는 원래 줄 기준
+        assert_eq!(banks[0].select_pair().unwrap().indices, vec![0, 1]);
+        assert_eq!(banks[0].select(3).unwrap().indices, vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn test_max_joltage_k_big() {
+        // k = 100: 앞의 9 50개와 뒤의 1 50개, u64를 넘는 100자리
+        let bank = "9".repeat(50) + &"1".repeat(100);
+        let expected = "9".repeat(50) + &"1".repeat(50);
+        assert_eq!(max_joltage_k_big(&bank, 100).to_string(), expected);
+        assert_eq!(max_joltage_k_big(&bank, 151), BigUint::default());
+        // k ≤ 19이면 u64 버전과 같음
+        assert_eq!(
+            max_joltage_k_big("234234234234278", 12).to_u64(),
+            Some(max_joltage_k("234234234234278", 12))
+        );
+    }
+
+    #[test]
+    fn test_solve_part2_k_big() {
+        // 9가 100개인 뱅크 두 개: 2 · (10^100 - 1) = 1 99...9 8
+        let bank = "9".repeat(100);
+        let input = format!("{}\n{}", bank, bank);
+        let expected = "1".to_string() + &"9".repeat(99) + "8";
+        assert_eq!(solve_part2_k(&input, 100).to_string(), expected);
+    }
+
+    #[test]
+    fn test_big_uint_decimal_round_trip() {
+        for digits in [
+            "0",
+            "7",
+            "999999999",
+            "1000000000",
+            "123456789012345678901234567890",
+        ] {
+            assert_eq!(BigUint::from_decimal(digits).unwrap().to_string(), digits);
+        }
+        // 앞의 0은 버림, 빈 문자열은 0
+        assert_eq!(
+            BigUint::from_decimal("000000000000042")
+                .unwrap()
+                .to_string(),
+            "42"
+        );
+        assert_eq!(BigUint::from_decimal("").unwrap(), BigUint::default());
+        assert_eq!(BigUint::from_decimal("12a"), None);
+    }
+
+    #[test]
+    fn test_big_uint_to_u64() {
+        assert_eq!(BigUint::from(u64::MAX).to_u64(), Some(u64::MAX));
+        assert_eq!(BigUint::from(0).to_u64(), Some(0));
+        assert_eq!((BigUint::from(u64::MAX) + BigUint::from(1)).to_u64(), None);
+    }
+
+    #[test]
+    fn test_big_uint_add_matches_u128() {
+        // 무작위 수의 합을 u128 합과 비교, 올림이 여러 limb로 번지는 경우 포함
+        let mut rng = Rng(0xD1B5_4A32_D192_ED03);
+        let mut total = BigUint::default();
+        let mut expected = 0u128;
+        for _ in 0..1000 {
+            let value = match rng.below(3) {
+                0 => u64::MAX - rng.below(10),
+                1 => rng.below(LIMB_BASE as u64),
+                _ => rng.next(),
+            };
+            total += &BigUint::from(value);
+            expected += value as u128;
+        }
+        assert_eq!(total.to_string(), expected.to_string());
+        // 999...9 + 1 = 1000...0
+        let nines = BigUint::from_decimal(&"9".repeat(40)).unwrap();
+        assert_eq!(
+            (nines + BigUint::from(1)).to_string(),
+            "1".to_string() + &"0".repeat(40)
+        );
+    }
+}